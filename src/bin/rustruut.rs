@@ -0,0 +1,198 @@
+use clap::{Parser, ValueEnum};
+use rustruut::{di, DependencyInjection, PhonemizeSentenceReq, PhonemizeSentenceResp, Phonemizer};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Phonemize text to IPA (or IPA back to text) with goruut.
+///
+/// Every non-empty line of the input is phonemized as one sentence and written
+/// out right away. The input is taken from the TEXT arguments, else from the
+/// --file arguments, else from stdin.
+#[derive(Debug, Parser)]
+#[command(name = "rustruut")]
+struct Cli {
+    /// Text to phonemize, each argument is one sentence
+    text: Vec<String>,
+
+    /// Read sentences from a file, may be repeated
+    #[arg(short, long = "file", value_name = "PATH")]
+    files: Vec<PathBuf>,
+
+    /// Language of the input; several comma separated languages enable multi-lingual handling
+    #[arg(short, long, value_delimiter = ',', required = true)]
+    language: Vec<String>,
+
+    /// Convert IPA back to text
+    #[arg(short, long)]
+    reverse: bool,
+
+    /// IPA flavor to apply to the output, may be repeated
+    #[arg(short, long = "ipa-flavor", value_name = "FLAVOR")]
+    ipa_flavors: Vec<String>,
+
+    /// Goruut release to use, e.g. v0.7.0 (defaults to $RUSTRUUT_VERSION, else the newest)
+    #[arg(short = 'V', long)]
+    version: Option<String>,
+
+    /// Use a running goruut server or online API instead of a local goruut
+    #[arg(short, long, value_name = "URL")]
+    api: Option<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Plain)]
+    output: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Phonetic words of each sentence on one line
+    Plain,
+    /// One JSON array holding the response of every sentence
+    Json,
+    /// One JSON response per line
+    Jsonl,
+}
+
+type CliPhonemizer = Phonemizer<
+    di::default_impls::DummyPolicy,
+    di::default_impls::DummyIpaFlavor,
+    di::default_impls::DummyDict,
+    di::custom_impls::CustomApi,
+    di::custom_impls::CustomFolder,
+    di::custom_impls::CustomVersion,
+>;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("rustruut: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns whether every sentence was phonemized.
+fn run(cli: &Cli) -> io::Result<bool> {
+    let version = match &cli.version {
+        Some(v) => di::custom_impls::CustomVersion::new(v),
+        None => di::custom_impls::CustomVersion::default(),
+    };
+    let di = DependencyInjection::with_parts(
+        di::default_impls::DummyPolicy,
+        di::default_impls::DummyIpaFlavor,
+        di::default_impls::DummyDict,
+        di::custom_impls::CustomApi::new(cli.api.as_deref().unwrap_or_default()),
+        di::custom_impls::CustomFolder,
+        version,
+    );
+    let p: CliPhonemizer = Phonemizer::new(di);
+
+    // Every line is written as soon as it is phonemized, the input is never held whole
+    let mut out = Output::new(io::stdout().lock(), cli.output);
+    let mut ok = true;
+    for sentence in read_input(cli) {
+        let sentence = sentence?;
+        if sentence.trim().is_empty() {
+            continue;
+        }
+        let req = PhonemizeSentenceReq {
+            ipa_flavors: cli.ipa_flavors.clone(),
+            language: String::new(),
+            languages: cli.language.clone(),
+            sentence,
+            is_reverse: cli.reverse,
+            split_sentences: false,
+        };
+        let resp = match p.sentence(req) {
+            Ok(resp) => resp,
+            Err(e) => {
+                eprintln!("rustruut: {}", e);
+                ok = false;
+                PhonemizeSentenceResp::default()
+            }
+        };
+        out.write(&resp)?;
+    }
+    out.finish()?;
+    Ok(ok)
+}
+
+/// Input lines, read lazily from the TEXT arguments, the files or stdin.
+fn read_input(cli: &Cli) -> Box<dyn Iterator<Item = io::Result<String>> + '_> {
+    if !cli.text.is_empty() {
+        Box::new(cli.text.iter().cloned().map(Ok))
+    } else if !cli.files.is_empty() {
+        Box::new(cli.files.iter().flat_map(|path| {
+            let with_path =
+                move |e: io::Error| io::Error::new(e.kind(), format!("{}: {}", path.display(), e));
+            let lines: Box<dyn Iterator<Item = io::Result<String>>> = match File::open(path) {
+                Ok(file) => Box::new(
+                    BufReader::new(file)
+                        .lines()
+                        .map(move |l| l.map_err(with_path)),
+                ),
+                Err(e) => Box::new(std::iter::once(Err(with_path(e)))),
+            };
+            lines
+        }))
+    } else {
+        Box::new(io::stdin().lock().lines())
+    }
+}
+
+/// Writes responses in the chosen format as they come in.
+struct Output<W: Write> {
+    out: W,
+    format: Format,
+    written: usize,
+}
+
+impl<W: Write> Output<W> {
+    fn new(out: W, format: Format) -> Self {
+        Self {
+            out,
+            format,
+            written: 0,
+        }
+    }
+
+    fn write(&mut self, resp: &PhonemizeSentenceResp) -> io::Result<()> {
+        match self.format {
+            Format::Plain => {
+                let words: Vec<String> = resp
+                    .words
+                    .iter()
+                    .map(|w| format!("{}{}{}", w.pre_punct, w.phonetic, w.post_punct))
+                    .collect();
+                writeln!(self.out, "{}", words.join(" "))?;
+            }
+            Format::Json => {
+                // Elements of the array are written one by one
+                self.out
+                    .write_all(if self.written == 0 { b"[\n" } else { b",\n" })?;
+                serde_json::to_writer_pretty(&mut self.out, resp)?;
+            }
+            Format::Jsonl => {
+                serde_json::to_writer(&mut self.out, resp)?;
+                writeln!(self.out)?;
+            }
+        }
+        self.written += 1;
+        self.out.flush()
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        if self.format == Format::Json {
+            match self.written {
+                0 => writeln!(self.out, "[]")?,
+                _ => writeln!(self.out, "\n]")?,
+            }
+        }
+        self.out.flush()
+    }
+}
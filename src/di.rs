@@ -88,6 +88,20 @@ where
     }
 }
 
+impl<P, I, D, A, F, V> Default for DependencyInjection<P, I, D, A, F, V>
+where
+    P: PolicyMaxWords + Default,
+    I: IpaFlavor + Default,
+    D: DictGetter + Default,
+    A: Api + Default,
+    F: Folder + Default,
+    V: Version + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<P, I, D, A, F, V> DependencyInjection<P, I, D, A, F, V>
 where
     P: PolicyMaxWords,
@@ -110,7 +124,8 @@ where
 }
 
 pub mod custom_impls {
-    use super::super::interfaces::{Api, DictGetter, Folder, IpaFlavor, PolicyMaxWords, Version};
+    use super::super::interfaces::{Api, DictGetter, Folder, Version};
+    use std::path::{Component, Path, PathBuf};
 
     #[derive(Debug, Clone, Default)]
     pub struct CustomFolder;
@@ -121,6 +136,46 @@ pub mod custom_impls {
         }
     }
 
+    /// Runs a pre-installed goruut executable, nothing is downloaded.
+    ///
+    /// Config files are written to the download dir, the temp dir unless set.
+    #[derive(Debug, Clone)]
+    pub struct CustomExecutable {
+        executable_path: String,
+        download_dir: Option<String>,
+    }
+
+    impl CustomExecutable {
+        pub fn new(executable_path: &str) -> Self {
+            Self {
+                executable_path: executable_path.to_string(),
+                download_dir: None,
+            }
+        }
+
+        pub fn with_download_dir(mut self, download_dir: &str) -> Self {
+            self.download_dir = Some(download_dir.to_string());
+            self
+        }
+    }
+
+    impl Default for CustomExecutable {
+        fn default() -> Self {
+            let executable_path = std::env::var(crate::usecases::rustruut::EXECUTABLE_ENV).ok();
+            Self::new(&executable_path.unwrap_or_default())
+        }
+    }
+
+    impl Folder for CustomExecutable {
+        fn get_download_dir(&self) -> Option<&str> {
+            self.download_dir.as_deref()
+        }
+
+        fn get_executable_path(&self) -> Option<&str> {
+            Some(self.executable_path.as_str()).filter(|path| !path.is_empty())
+        }
+    }
+
     #[derive(Debug, Clone)]
     pub struct CustomVersion {
         version: String,
@@ -149,23 +204,101 @@ pub mod custom_impls {
         }
     }
 
+    /// Downloads goruut through mirrors, see `Version::get_mirrors` for the layout.
     #[derive(Debug, Clone)]
-    pub struct CustomApi {
-        api_path: String,
+    pub struct CustomMirrors {
+        version: CustomVersion,
+        mirrors: Vec<String>,
+        mirrors_only: bool,
     }
 
-    impl CustomApi {
-        pub fn new(api_path: &str) -> Self {
+    impl CustomMirrors {
+        pub fn new<S: AsRef<str>>(mirrors: impl IntoIterator<Item = S>) -> Self {
             Self {
-                api_path: api_path.to_string(),
+                version: CustomVersion::new(""),
+                mirrors: mirrors
+                    .into_iter()
+                    .map(|m| m.as_ref().to_string())
+                    .collect(),
+                mirrors_only: false,
             }
         }
+
+        pub fn with_version(mut self, version: &str) -> Self {
+            self.version = CustomVersion::new(version);
+            self
+        }
+
+        /// Replace the release servers instead of trying the mirrors first.
+        pub fn mirrors_only(mut self) -> Self {
+            self.mirrors_only = true;
+            self
+        }
     }
 
-    impl Default for CustomApi {
+    /// Mirrors from `RUSTRUUT_MIRRORS` (separated by commas or whitespace),
+    /// the version from `RUSTRUUT_VERSION`.
+    impl Default for CustomMirrors {
         fn default() -> Self {
+            let mirrors = std::env::var("RUSTRUUT_MIRRORS").unwrap_or_default();
+            Self {
+                version: CustomVersion::default(),
+                ..Self::new(
+                    mirrors
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|m| !m.is_empty()),
+                )
+            }
+        }
+    }
+
+    impl Version for CustomMirrors {
+        fn get_version(&self) -> Option<&str> {
+            self.version.get_version()
+        }
+
+        fn get_mirrors(&self) -> &[String] {
+            &self.mirrors
+        }
+
+        fn mirrors_only(&self) -> bool {
+            self.mirrors_only
+        }
+    }
+
+    /// Serves model and dictionary files from a directory, `English.zlib` is read
+    /// from `<dir>/English.zlib` and `English.tsv` from `<dir>/English.tsv`.
+    #[derive(Debug, Clone)]
+    pub struct CustomDict {
+        dir: PathBuf,
+    }
+
+    impl CustomDict {
+        pub fn new(dir: impl Into<PathBuf>) -> Self {
+            Self { dir: dir.into() }
+        }
+    }
+
+    impl DictGetter for CustomDict {
+        fn get(&self, path: &str) -> Option<Vec<u8>> {
+            // Only plain relative paths, nothing outside of the directory
+            let path = Path::new(path);
+            if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+                return None;
+            }
+            std::fs::read(self.dir.join(path)).ok()
+        }
+    }
+
+    #[derive(Debug, Clone, Default)]
+    pub struct CustomApi {
+        api_path: String,
+    }
+
+    impl CustomApi {
+        pub fn new(api_path: &str) -> Self {
             Self {
-                api_path: String::new(),
+                api_path: api_path.to_string(),
             }
         }
     }
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use crate::ipa::{tokenize, Phoneme};
+use crate::transcription::{Converter, Notation, Unmappable};
+use crate::usecases::languages::Language;
+use crate::usecases::rustruut::RustruutError;
+
+/// Layout of an exported pronunciation dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictFormat {
+    /// Montreal Forced Aligner: `word<TAB>p1 p2 p3`.
+    Mfa,
+    /// Kaldi `lexicon.txt`: `word p1 p2 p3`.
+    Kaldi,
+    /// CMUdict style: `WORD  P1 P2 P3` in ARPAbet with stress digits, repeated
+    /// words as `WORD(1)`. English only, a pronunciation with a sound ARPAbet
+    /// has no symbol for is not written.
+    CmuDict,
+    /// `word<TAB>phonetic` with goruut's unsegmented output.
+    Tsv,
+}
+
+impl DictFormat {
+    /// Whether pronunciations of `language` can be written, ARPAbet only has
+    /// symbols for English.
+    pub fn supports(self, language: &str) -> bool {
+        match self {
+            DictFormat::CmuDict => {
+                Language::parse(language).is_ok_and(|l| l.name().starts_with("English"))
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Outcome of a dictionary export.
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    /// Number of entries written.
+    pub written: usize,
+    /// Words which could not be phonemized, they are left out of the dictionary.
+    pub failed: Vec<(String, RustruutError)>,
+}
+
+/// Writes dictionary entries in one of the supported formats.
+pub struct DictWriter<W: Write> {
+    out: W,
+    format: DictFormat,
+    // CMUdict keys already written, to number alternative pronunciations
+    seen: HashSet<String>,
+}
+
+impl<W: Write> DictWriter<W> {
+    pub fn new(out: W, format: DictFormat) -> Self {
+        Self {
+            out,
+            format,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Write the pronunciation of `word`, `phonetic` is goruut's IPA output.
+    ///
+    /// Fails with `RustruutError::Conversion` when `phonetic` has no CMUdict
+    /// spelling, nothing is written then.
+    pub fn write(&mut self, word: &str, phonetic: &str) -> Result<(), RustruutError> {
+        let phonemes = match self.format {
+            DictFormat::CmuDict => arpabet(phonetic)?,
+            _ => {
+                let phonemes: Vec<String> =
+                    tokenize(phonetic).iter().map(Phoneme::symbol).collect();
+                phonemes.join(" ")
+            }
+        };
+        match self.format {
+            DictFormat::Mfa => writeln!(self.out, "{}\t{}", word, phonemes)?,
+            DictFormat::Kaldi => writeln!(self.out, "{} {}", word, phonemes)?,
+            DictFormat::CmuDict => {
+                let word = word.to_uppercase();
+                let mut key = word.clone();
+                let mut variant = 0;
+                while !self.seen.insert(key.clone()) {
+                    variant += 1;
+                    key = format!("{}({})", word, variant);
+                }
+                writeln!(self.out, "{}  {}", key, phonemes)?
+            }
+            DictFormat::Tsv => writeln!(self.out, "{}\t{}", word, phonetic)?,
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+/// ARPAbet symbols of all words in `phonetic`, as one space separated list.
+fn arpabet(phonetic: &str) -> Result<String, RustruutError> {
+    let converter = Converter::new(Notation::Arpabet).with_unmappable(Unmappable::Fail);
+    let words = phonetic
+        .split_whitespace()
+        .map(|word| converter.from_ipa(word))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(words.join(" "))
+}
@@ -24,9 +24,27 @@ pub trait Api: Send + Sync + Clone {
 /// Trait that provides Download folder path
 pub trait Folder: Send + Sync + Clone {
     fn get_download_dir(&self) -> Option<&str>;
+
+    /// Path of a pre-installed goruut executable to run instead of downloading one.
+    fn get_executable_path(&self) -> Option<&str> {
+        None
+    }
 }
 
 /// Trait that provides version information
 pub trait Version: Send + Sync + Clone {
     fn get_version(&self) -> Option<&str>;
+
+    /// Mirrors tried before the release servers, HTTP URLs or `file://` directories.
+    ///
+    /// A release is looked up as `<mirror>/<version>/<public file name>`, for
+    /// example `file:///srv/goruut/v0.8.0/goruut-linux-amd64`.
+    fn get_mirrors(&self) -> &[String] {
+        &[]
+    }
+
+    /// Download from the mirrors only, never from the release servers.
+    fn mirrors_only(&self) -> bool {
+        false
+    }
 }
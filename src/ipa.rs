@@ -0,0 +1,179 @@
+use std::fmt;
+
+const VOWELS: &str = "iyɨʉɯuɪʏʊeøɘɵɤoəɛœɜɞʌɔæɐaɶɑɒɚɝᵻᵿ";
+
+/// Stress of the syllable a phoneme starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Stress {
+    #[default]
+    None,
+    /// `ˈ`
+    Primary,
+    /// `ˌ`
+    Secondary,
+}
+
+/// Duration mark of a phoneme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Length {
+    /// Combining breve `◌̆`.
+    ExtraShort,
+    #[default]
+    Normal,
+    /// `ˑ`
+    HalfLong,
+    /// `ː`
+    Long,
+    /// `ːː`
+    ExtraLong,
+}
+
+/// One IPA segment.
+///
+/// # Examples
+///
+/// ```
+/// use rustruut::ipa::{tokenize, Length, Stress};
+/// let phonemes = tokenize("ˈt͡ʃɑːʰ");
+/// assert_eq!(phonemes[0].base, "t͡ʃ");
+/// assert_eq!(phonemes[0].stress, Stress::Primary);
+/// assert_eq!(phonemes[1].length, Length::Long);
+/// assert_eq!(phonemes[1].diacritics, "ʰ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Phoneme {
+    /// Base symbol, affricates and other tie-bar sequences included (`t͡ʃ`).
+    pub base: String,
+    /// Combining diacritics and modifier letters, in input order.
+    pub diacritics: String,
+    pub stress: Stress,
+    pub length: Length,
+}
+
+impl Phoneme {
+    /// The phoneme without its stress mark, e.g. `pʰ` or `ɑː`.
+    pub fn symbol(&self) -> String {
+        let mut symbol = self.base.clone();
+        symbol.push_str(&self.diacritics);
+        symbol.push_str(match self.length {
+            Length::ExtraShort => "\u{0306}",
+            Length::Normal => "",
+            Length::HalfLong => "ˑ",
+            Length::Long => "ː",
+            Length::ExtraLong => "ːː",
+        });
+        symbol
+    }
+
+    /// Whether the base symbol is a vowel letter.
+    pub fn is_vowel(&self) -> bool {
+        self.base.chars().next().is_some_and(|c| VOWELS.contains(c))
+    }
+
+    /// Whether the phoneme can be a syllable nucleus: a vowel without the
+    /// non-syllabic mark `◌̯`, or a consonant with the syllabic mark `◌̩`.
+    pub fn is_syllabic(&self) -> bool {
+        if self.is_vowel() {
+            !self.diacritics.contains('\u{032F}')
+        } else {
+            self.diacritics.contains(['\u{0329}', '\u{030D}'])
+        }
+    }
+}
+
+impl fmt::Display for Phoneme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.stress {
+            Stress::None => {}
+            Stress::Primary => f.write_str("ˈ")?,
+            Stress::Secondary => f.write_str("ˌ")?,
+        }
+        f.write_str(&self.symbol())
+    }
+}
+
+/// Split an IPA string into phonemes.
+///
+/// Diacritics, length marks and modifier letters belong to the preceding
+/// symbol, tie bars join the symbols around them. Stress marks apply to the
+/// next phoneme. Whitespace, syllable breaks, linking and intonation marks and
+/// punctuation separate phonemes but are not part of any.
+pub fn tokenize(ipa: &str) -> Vec<Phoneme> {
+    let mut phonemes: Vec<Phoneme> = Vec::new();
+    let mut stress = Stress::None;
+    let mut tied = false;
+    // whether the next symbol starts a new phoneme
+    let mut boundary = true;
+
+    for c in ipa.chars() {
+        match c {
+            'ˈ' => {
+                stress = Stress::Primary;
+                boundary = true;
+                continue;
+            }
+            'ˌ' => {
+                stress = Stress::Secondary;
+                boundary = true;
+                continue;
+            }
+            _ => {}
+        }
+        if c.is_whitespace() || is_separator(c) {
+            boundary = true;
+            tied = false;
+            continue;
+        }
+
+        let current = if boundary { None } else { phonemes.last_mut() };
+        match current {
+            Some(last) if is_tie(c) => {
+                last.base.push(c);
+                tied = true;
+            }
+            Some(last) if tied => {
+                last.base.push(c);
+                tied = false;
+            }
+            Some(last) if c == 'ː' => {
+                last.length = match last.length {
+                    Length::Long | Length::ExtraLong => Length::ExtraLong,
+                    _ => Length::Long,
+                };
+            }
+            Some(last) if c == 'ˑ' => last.length = Length::HalfLong,
+            Some(last) if c == '\u{0306}' => last.length = Length::ExtraShort,
+            Some(last) if is_diacritic(c) => last.diacritics.push(c),
+            _ => {
+                phonemes.push(Phoneme {
+                    base: c.to_string(),
+                    stress,
+                    ..Default::default()
+                });
+                stress = Stress::None;
+                boundary = false;
+            }
+        }
+    }
+    phonemes
+}
+
+fn is_tie(c: char) -> bool {
+    matches!(c, '\u{0361}' | '\u{035C}')
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '.' | '‿' | '|' | '‖' | '↗' | '↘') || c.is_ascii_punctuation()
+}
+
+fn is_diacritic(c: char) -> bool {
+    let combining = matches!(
+        c as u32,
+        0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF
+    );
+    combining
+        || matches!(
+            c,
+            'ʰ' | 'ʱ' | 'ʷ' | 'ʲ' | 'ˠ' | 'ˤ' | 'ⁿ' | 'ˡ' | 'ʼ' | '˞' | 'ᵊ'
+        )
+}
@@ -0,0 +1,333 @@
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use thiserror::Error;
+
+use crate::models::pos_tag::PosTags;
+use crate::models::{requests, responses};
+use crate::usecases::languages::{Language, LanguageError};
+
+/// Punctuation stripped from a token before it is looked up.
+const PUNCT: &[char] = &[
+    '«', '»', '„', '“', '”', '‘', '’', '¿', '¡', '…', '—', '–', '。', '、', '，', '！', '？', '؟',
+    '،',
+];
+
+#[derive(Error, Debug)]
+pub enum LexiconError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serde JSON error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("Language error: {0}")]
+    Language(#[from] LanguageError),
+    #[error("Line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+#[derive(Debug, Clone, Default)]
+struct Entry {
+    // the word as last inserted, for reverse requests
+    spelling: String,
+    // pronunciation regardless of part of speech
+    phonetic: Option<String>,
+    // upper-case POS tag -> pronunciation
+    tagged: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct LanguageLexicon {
+    // lower-case word -> entry
+    words: HashMap<String, Entry>,
+    // pronunciation -> spelling, for reverse requests
+    spellings: HashMap<String, String>,
+}
+
+impl LanguageLexicon {
+    /// Set the pronunciation of `word`, the one for `pos` when given.
+    fn set(&mut self, word: &str, pos: Option<&str>, phonetic: &str) {
+        let entry = self.words.entry(word.to_lowercase()).or_default();
+        entry.spelling = word.to_string();
+        let previous = match pos {
+            Some(pos) => entry
+                .tagged
+                .insert(pos.to_uppercase(), phonetic.to_string()),
+            None => entry.phonetic.replace(phonetic.to_string()),
+        };
+        self.spellings
+            .insert(phonetic.to_string(), word.to_string());
+        if let Some(previous) = previous.filter(|p| p != phonetic) {
+            self.respell(&previous);
+        }
+    }
+
+    /// Point a pronunciation which was replaced at another word still using
+    /// it, or forget it.
+    fn respell(&mut self, phonetic: &str) {
+        let spelling = self
+            .words
+            .iter()
+            .filter(|(_, e)| {
+                e.phonetic.as_deref() == Some(phonetic) || e.tagged.values().any(|p| p == phonetic)
+            })
+            .min_by_key(|(word, _)| word.as_str())
+            .map(|(_, e)| e.spelling.clone());
+        match spelling {
+            Some(spelling) => self.spellings.insert(phonetic.to_string(), spelling),
+            None => self.spellings.remove(phonetic),
+        };
+    }
+}
+
+/// JSON lexicon entry, `[{"Word": "rustruut", "Phonetic": "ɹʌstɹuːt"}]`.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct JsonEntry {
+    word: String,
+    phonetic: String,
+    #[serde(default)]
+    pos_tag: Option<String>,
+}
+
+/// User pronunciations which take precedence over goruut, per language.
+///
+/// Words match case-insensitively. goruut still phonemizes the whole sentence
+/// for context, entries without a POS tag then replace its pronunciation and
+/// tagged entries do so when the word was tagged accordingly. A sentence of
+/// overridden words only is answered without asking goruut. Reverse requests
+/// map an overridden pronunciation back to the spelling it was inserted with.
+///
+/// # Examples
+///
+/// ```
+/// use rustruut::Lexicon;
+/// let mut lexicon = Lexicon::new();
+/// lexicon.insert("English", "Rustruut", "ɹʌstɹuːt")?;
+/// lexicon.insert_tagged("English", "read", "VERB", "ɹiːd")?;
+/// assert_eq!(lexicon.lookup("English", "RUSTRUUT", None), Some("ɹʌstɹuːt"));
+/// # Ok::<(), rustruut::LexiconError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Lexicon {
+    languages: BTreeMap<String, LanguageLexicon>,
+}
+
+impl Lexicon {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the pronunciation of `word` in every context.
+    pub fn insert(
+        &mut self,
+        language: &str,
+        word: &str,
+        phonetic: &str,
+    ) -> Result<(), LexiconError> {
+        self.language_mut(language)?.set(word, None, phonetic);
+        Ok(())
+    }
+
+    /// Override the pronunciation of `word` where goruut tags it with `pos`.
+    pub fn insert_tagged(
+        &mut self,
+        language: &str,
+        word: &str,
+        pos: &str,
+        phonetic: &str,
+    ) -> Result<(), LexiconError> {
+        self.language_mut(language)?.set(word, Some(pos), phonetic);
+        Ok(())
+    }
+
+    /// Load `word<TAB>phonetic[<TAB>pos]` lines, `#` starts a comment line.
+    pub fn load_tsv(&mut self, language: &str, path: &Path) -> Result<(), LexiconError> {
+        let text = std::fs::read_to_string(path)?;
+        self.parse_tsv(language, &text)
+    }
+
+    pub fn parse_tsv(&mut self, language: &str, text: &str) -> Result<(), LexiconError> {
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            match fields.as_slice() {
+                [word, phonetic] => self.insert(language, word, phonetic)?,
+                [word, phonetic, pos] => self.insert_tagged(language, word, pos, phonetic)?,
+                _ => {
+                    return Err(LexiconError::Parse {
+                        line: i + 1,
+                        message: format!("expected 2 or 3 tab separated fields: {:?}", line),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Load a JSON array of `{"Word", "Phonetic", "PosTag"}` objects, `PosTag` is optional.
+    pub fn load_json(&mut self, language: &str, path: &Path) -> Result<(), LexiconError> {
+        let text = std::fs::read_to_string(path)?;
+        self.parse_json(language, &text)
+    }
+
+    pub fn parse_json(&mut self, language: &str, text: &str) -> Result<(), LexiconError> {
+        let entries: Vec<JsonEntry> = serde_json::from_str(text)?;
+        for entry in entries {
+            match &entry.pos_tag {
+                Some(pos) => self.insert_tagged(language, &entry.word, pos, &entry.phonetic)?,
+                None => self.insert(language, &entry.word, &entry.phonetic)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the entries of `other`, its pronunciations replace those of the same
+    /// word and POS tag.
+    pub(crate) fn extend(&mut self, other: &Lexicon) {
+        for (language, lexicon) in &other.languages {
+            let target = self.languages.entry(language.clone()).or_default();
+            for entry in lexicon.words.values() {
+                if let Some(phonetic) = &entry.phonetic {
+                    target.set(&entry.spelling, None, phonetic);
+                }
+                for (pos, phonetic) in &entry.tagged {
+                    target.set(&entry.spelling, Some(pos), phonetic);
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.languages.values().all(|l| l.words.is_empty())
+    }
+
+    /// Pronunciation of `word`, the `pos` specific one first when a tag is given.
+    pub fn lookup(&self, language: &str, word: &str, pos: Option<&str>) -> Option<&str> {
+        let entry = self.entry(language, word)?;
+        pos.and_then(|pos| entry.tagged.get(&pos.to_uppercase()))
+            .or(entry.phonetic.as_ref())
+            .map(String::as_str)
+    }
+
+    /// Spelling of an overridden pronunciation.
+    pub fn spelling(&self, language: &str, phonetic: &str) -> Option<&str> {
+        self.language(language)?
+            .spellings
+            .get(phonetic)
+            .map(String::as_str)
+    }
+
+    /// Stable hash of all entries, changes whenever the lexicon does.
+    pub(crate) fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        for (language, lexicon) in &self.languages {
+            let mut words: Vec<_> = lexicon.words.iter().collect();
+            words.sort_by(|a, b| a.0.cmp(b.0));
+            for (word, entry) in words {
+                let line = format!(
+                    "{}\t{}\t{}\t{:?}\t{:?}\n",
+                    language, word, entry.spelling, entry.phonetic, entry.tagged
+                );
+                hasher.update(line.as_bytes());
+            }
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Answer a whitespace separated token of the request without goruut,
+    /// only possible for entries without a POS tag.
+    pub(crate) fn short_circuit(
+        &self,
+        req: &requests::PhonemizeSentence,
+        token: &str,
+    ) -> Option<responses::PhonemizeSentenceWord> {
+        let (pre, word, post) = split_punct(token);
+        let phonetic = if req.is_reverse {
+            self.spelling(&req.language, word)?
+        } else {
+            self.entry(&req.language, word)?.phonetic.as_deref()?
+        };
+        Some(responses::PhonemizeSentenceWord {
+            clean_word: word.to_string(),
+            phonetic: phonetic.to_string(),
+            pre_punct: pre.to_string(),
+            post_punct: post.to_string(),
+            ..Default::default()
+        })
+    }
+
+    /// Replace goruut's pronunciations of overridden words in a response.
+    pub(crate) fn apply(
+        &self,
+        req: &requests::PhonemizeSentence,
+        resp: &mut responses::PhonemizeSentence,
+    ) {
+        if self.language(&req.language).is_none() {
+            return;
+        }
+        for word in &mut resp.words {
+            if req.is_reverse {
+                if let Some(spelling) = self.spelling(&req.language, &word.clean_word) {
+                    word.phonetic = spelling.to_string();
+                }
+                continue;
+            }
+            let Some(entry) = self.entry(&req.language, &word.clean_word) else {
+                continue;
+            };
+            let tagged = word.pos_tags.as_ref().and_then(|tags| {
+                entry
+                    .tagged
+                    .iter()
+                    .find(|(pos, _)| has_tag(tags, pos))
+                    .map(|(_, phonetic)| phonetic)
+            });
+            if let Some(phonetic) = tagged.or(entry.phonetic.as_ref()) {
+                word.phonetic = phonetic.clone();
+            }
+        }
+    }
+
+    fn language(&self, language: &str) -> Option<&LanguageLexicon> {
+        match self.languages.get(language) {
+            Some(lexicon) => Some(lexicon),
+            None => {
+                let language = Language::parse(language).ok()?;
+                self.languages.get(language.name())
+            }
+        }
+    }
+
+    fn language_mut(&mut self, language: &str) -> Result<&mut LanguageLexicon, LexiconError> {
+        let language = Language::parse(language)?;
+        Ok(self
+            .languages
+            .entry(language.name().to_string())
+            .or_default())
+    }
+
+    fn entry(&self, language: &str, word: &str) -> Option<&Entry> {
+        self.language(language)?.words.get(&word.to_lowercase())
+    }
+}
+
+fn is_punct(c: char) -> bool {
+    c.is_ascii_punctuation() || PUNCT.contains(&c)
+}
+
+/// Split a token into leading punctuation, the word and trailing punctuation.
+pub(crate) fn split_punct(token: &str) -> (&str, &str, &str) {
+    let word = token.trim_start_matches(is_punct);
+    let pre = &token[..token.len() - word.len()];
+    let trimmed = word.trim_end_matches(is_punct);
+    (pre, trimmed, &word[trimmed.len()..])
+}
+
+fn has_tag(tags: &PosTags, pos: &str) -> bool {
+    tags.tags()
+        .iter()
+        .any(|tag| tag.as_str().eq_ignore_ascii_case(pos))
+}
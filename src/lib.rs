@@ -2,25 +2,58 @@
 
 // PUBLIC
 pub mod di;
+pub mod export;
 pub mod interfaces;
+pub mod ipa;
+pub mod lexicon;
+pub mod options;
+pub mod phoneme_ids;
 pub mod phonemize;
+pub mod syllable;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+pub mod transcription;
 pub mod models {
+    pub mod pos_tag;
     pub mod requests;
     pub mod responses;
 }
 
 pub use di::DependencyInjection;
+pub use export::{DictFormat, DictWriter, ExportReport};
 pub use interfaces::{Api, DictGetter, Folder, IpaFlavor, PolicyMaxWords, Version};
+pub use lexicon::{Lexicon, LexiconError};
+pub use models::pos_tag::{PosTag, PosTags};
 pub use models::requests::PhonemizeSentence as PhonemizeSentenceReq;
-pub use models::responses::{PhonemizeSentence as PhonemizeSentenceResp, PhonemizeSentenceWord};
+pub use models::responses::{
+    PhonemizeSentence as PhonemizeSentenceResp, PhonemizeSentenceWord, WordOffsets,
+};
+pub use options::{ClientOptions, DiskCacheOptions, PhonemizerOptions, RestartPolicy};
 pub use phonemize::Phonemizer;
+pub use usecases::languages::{Language, LanguageError, Languages};
+pub use usecases::word_cache::WordCacheStats;
+#[cfg(feature = "async")]
+pub use phonemize::AsyncPhonemizer;
 
 // INTERNAL USE
 pub mod usecases {
+    pub mod cache;
+    pub mod chunking;
     pub mod config;
+    pub mod dict;
     pub mod executable;
+    pub mod ipa_flavor;
+    pub mod languages;
+    pub mod offsets;
     pub mod phonemize;
+    pub mod pipeline;
     pub mod platform;
     pub mod release;
     pub mod rustruut;
+    #[cfg(feature = "async")]
+    pub mod rustruut_async;
+    pub mod supervisor;
+    #[cfg(feature = "async")]
+    pub mod supervisor_async;
+    pub mod word_cache;
 }
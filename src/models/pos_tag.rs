@@ -0,0 +1,179 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// Universal Dependencies part-of-speech tag.
+///
+/// Tags are recognized in any case, tags outside the UD tagset are kept
+/// verbatim in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PosTag {
+    Adj,
+    Adp,
+    Adv,
+    Aux,
+    Cconj,
+    Det,
+    Intj,
+    Noun,
+    Num,
+    Part,
+    Pron,
+    Propn,
+    Punct,
+    Sconj,
+    Sym,
+    Verb,
+    X,
+    Other(String),
+}
+
+impl PosTag {
+    pub fn parse(tag: &str) -> Self {
+        match tag.to_ascii_uppercase().as_str() {
+            "ADJ" => PosTag::Adj,
+            "ADP" => PosTag::Adp,
+            "ADV" => PosTag::Adv,
+            "AUX" => PosTag::Aux,
+            "CCONJ" => PosTag::Cconj,
+            "DET" => PosTag::Det,
+            "INTJ" => PosTag::Intj,
+            "NOUN" => PosTag::Noun,
+            "NUM" => PosTag::Num,
+            "PART" => PosTag::Part,
+            "PRON" => PosTag::Pron,
+            "PROPN" => PosTag::Propn,
+            "PUNCT" => PosTag::Punct,
+            "SCONJ" => PosTag::Sconj,
+            "SYM" => PosTag::Sym,
+            "VERB" => PosTag::Verb,
+            "X" => PosTag::X,
+            _ => PosTag::Other(tag.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            PosTag::Adj => "ADJ",
+            PosTag::Adp => "ADP",
+            PosTag::Adv => "ADV",
+            PosTag::Aux => "AUX",
+            PosTag::Cconj => "CCONJ",
+            PosTag::Det => "DET",
+            PosTag::Intj => "INTJ",
+            PosTag::Noun => "NOUN",
+            PosTag::Num => "NUM",
+            PosTag::Part => "PART",
+            PosTag::Pron => "PRON",
+            PosTag::Propn => "PROPN",
+            PosTag::Punct => "PUNCT",
+            PosTag::Sconj => "SCONJ",
+            PosTag::Sym => "SYM",
+            PosTag::Verb => "VERB",
+            PosTag::X => "X",
+            PosTag::Other(tag) => tag,
+        }
+    }
+}
+
+impl fmt::Display for PosTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PosTag {
+    type Err = Infallible;
+
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        Ok(PosTag::parse(tag))
+    }
+}
+
+impl Serialize for PosTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PosTag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tag = String::deserialize(deserializer)?;
+        Ok(PosTag::parse(&tag))
+    }
+}
+
+/// The `PosTags` of a word, in the shape goruut sent them.
+///
+/// goruut emits a single tag, an array of tags or nested arrays. The JSON is
+/// kept as sent next to the parsed tags, so serializing a response gives back
+/// exactly what goruut sent, spelling included.
+///
+/// # Examples
+///
+/// ```
+/// use rustruut::{PosTag, PosTags};
+/// let tags: PosTags = serde_json::from_str(r#"[["verb"], "NNP"]"#).unwrap();
+/// assert_eq!(tags.tags(), [PosTag::Verb, PosTag::Other("NNP".to_string())]);
+/// assert_eq!(serde_json::to_string(&tags).unwrap(), r#"[["verb"],"NNP"]"#);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PosTags {
+    raw: Value,
+    tags: Vec<PosTag>,
+}
+
+impl PosTags {
+    /// Every tag, nested arrays flattened. Values other than strings are kept
+    /// as their JSON text in `PosTag::Other`.
+    pub fn tags(&self) -> &[PosTag] {
+        &self.tags
+    }
+
+    /// The JSON as goruut sent it.
+    pub fn raw(&self) -> &Value {
+        &self.raw
+    }
+
+    pub fn contains(&self, tag: &PosTag) -> bool {
+        self.tags.contains(tag)
+    }
+}
+
+impl From<Value> for PosTags {
+    fn from(raw: Value) -> Self {
+        fn collect(value: &Value, tags: &mut Vec<PosTag>) {
+            match value {
+                Value::Null => {}
+                Value::String(tag) => tags.push(PosTag::parse(tag)),
+                Value::Array(values) => values.iter().for_each(|v| collect(v, tags)),
+                other => tags.push(PosTag::Other(other.to_string())),
+            }
+        }
+
+        let mut tags = Vec::new();
+        collect(&raw, &mut tags);
+        Self { raw, tags }
+    }
+}
+
+impl From<Vec<PosTag>> for PosTags {
+    fn from(tags: Vec<PosTag>) -> Self {
+        let raw = Value::Array(tags.iter().map(|t| Value::from(t.as_str())).collect());
+        Self { raw, tags }
+    }
+}
+
+impl Serialize for PosTags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.raw.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PosTags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(PosTags::from(Value::deserialize(deserializer)?))
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::usecases::languages::{Language, LanguageError};
+
 /// Request model for phonemizing a sentence.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -21,9 +23,33 @@ pub struct PhonemizeSentence {
 
 impl PhonemizeSentence {
     /// Init populates fields with sane defaults. Mirrors the Go Init() semantics.
-    pub fn init(&mut self) {
+    ///
+    /// Languages are validated and rewritten to their goruut names, so ISO codes
+    /// and any casing are accepted. An empty language is left for goruut to handle.
+    pub fn init(&mut self) -> Result<(), LanguageError> {
+        self.init_languages(false)
+    }
+
+    /// Like `init`, but languages missing from the registry are passed to goruut
+    /// as given, for languages of a goruut release newer than this crate.
+    pub fn init_lenient(&mut self) {
+        let _ = self.init_languages(true);
+    }
+
+    fn init_languages(&mut self, lenient: bool) -> Result<(), LanguageError> {
+        let canonical = |value: &str| match Language::parse(value) {
+            Ok(language) => Ok(language.name().to_string()),
+            Err(_) if lenient => Ok(value.to_string()),
+            Err(err) => Err(err),
+        };
+        for language in &mut self.languages {
+            *language = canonical(language)?;
+        }
         if self.language.is_empty() && !self.languages.is_empty() {
             self.language = self.languages[0].clone();
+        } else if !self.language.is_empty() {
+            self.language = canonical(&self.language)?;
         }
+        Ok(())
     }
 }
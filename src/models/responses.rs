@@ -1,21 +1,18 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
-/// Word-level response. `pos_tags` is stored as raw JSON so that when the
-/// response is serialized the raw JSON array is inlined (same behavior as
-/// Go's json.RawMessage).
+use crate::ipa::{tokenize, Phoneme};
+use crate::models::pos_tag::{PosTag, PosTags};
+
+/// Word-level response. `pos_tags` accepts every shape goruut emits and is
+/// serialized back unchanged.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "PascalCase")]
 pub struct PhonemizeSentenceWord {
     pub clean_word: String,
     pub phonetic: String,
 
-    #[serde(
-        default,
-        skip_serializing_if = "Option::is_none",
-        deserialize_with = "deserialize_null_as_none"
-    )]
-    pub pos_tags: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pos_tags: Option<PosTags>,
 
     #[serde(default)]
     pub pre_punct: String,
@@ -26,17 +23,33 @@ pub struct PhonemizeSentenceWord {
     pub is_first: bool,
     #[serde(default)]
     pub is_last: bool,
+
+    /// Position of `clean_word` in the request sentence, computed by rustruut.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offsets: Option<WordOffsets>,
 }
 
-/// Custom deserializer to handle null values for Option<Value>
-fn deserialize_null_as_none<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    let value = Value::deserialize(deserializer)?;
-    match value {
-        Value::Null => Ok(None),
-        _ => Ok(Some(value)),
+/// Half-open span of a word in the request sentence, in bytes and in chars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "PascalCase")]
+pub struct WordOffsets {
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+}
+
+impl PhonemizeSentenceWord {
+    /// The `phonetic` IPA split into phonemes.
+    pub fn phonemes(&self) -> Vec<Phoneme> {
+        tokenize(&self.phonetic)
+    }
+
+    /// Whether goruut tagged the word with `tag`.
+    pub fn has_pos_tag(&self, tag: &PosTag) -> bool {
+        self.pos_tags
+            .as_ref()
+            .is_some_and(|tags| tags.contains(tag))
     }
 }
 
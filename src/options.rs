@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use crate::lexicon::Lexicon;
+
+/// Runtime options of a `Phonemizer` which are not provided through the DI container.
+///
+/// # Examples
+///
+/// ```
+/// use rustruut::{PhonemizerOptions, RestartPolicy};
+/// let options = PhonemizerOptions {
+///     restart: RestartPolicy {
+///         max_restarts: 5,
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct PhonemizerOptions {
+    /// Supervision of the locally spawned goruut process.
+    pub restart: RestartPolicy,
+    /// Settings of the pooled HTTP client used to talk to goruut.
+    pub client: ClientOptions,
+    /// Number of concurrent requests issued by `Phonemizer::sentences`.
+    pub concurrency: usize,
+    /// Split sentences longer than `PolicyMaxWords` into chunks instead of
+    /// letting goruut reject them.
+    pub chunking: bool,
+    /// Return goruut's raw response with `error_word_limit_exceeded` set instead
+    /// of failing with `RustruutError::WordLimitExceeded`.
+    pub raw_word_limit: bool,
+    /// Persistent on-disk cache of whole-sentence results, `None` disables it.
+    pub disk_cache: Option<DiskCacheOptions>,
+    /// Number of words kept in the in-memory word cache, 0 disables it.
+    pub word_cache_capacity: usize,
+    /// User pronunciations taking precedence over goruut, `None` disables them.
+    pub lexicon: Option<Lexicon>,
+    /// Pass languages missing from the `Languages` registry to goruut as given
+    /// instead of failing with `RustruutError::Language`, for languages added by
+    /// a goruut release newer than this crate.
+    pub allow_unknown_languages: bool,
+    /// Check a pre-installed goruut executable against the sha256 of the known
+    /// releases before running it.
+    pub verify_executable: bool,
+}
+
+impl Default for PhonemizerOptions {
+    fn default() -> Self {
+        Self {
+            restart: RestartPolicy::default(),
+            client: ClientOptions::default(),
+            concurrency: 4,
+            chunking: false,
+            raw_word_limit: false,
+            disk_cache: None,
+            word_cache_capacity: 0,
+            lexicon: None,
+            allow_unknown_languages: false,
+            verify_executable: false,
+        }
+    }
+}
+
+/// Policy used when the goruut child process dies and has to be respawned.
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    /// Maximum number of consecutive restarts without a successful request in between.
+    pub max_restarts: usize,
+    /// Delay before the first restart attempt, doubled after every further attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between restart attempts.
+    pub max_backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Delay to wait before the given (1-based) restart attempt.
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let shift = attempt.saturating_sub(1).min(31) as u32;
+        self.initial_backoff
+            .saturating_mul(1u32 << shift)
+            .min(self.max_backoff)
+    }
+}
+
+/// Settings of the long-lived HTTP client shared by all requests of a `Phonemizer`.
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// Maximum number of idle keep-alive connections kept in the pool.
+    pub pool_max_idle_per_host: usize,
+    /// How long an idle pooled connection is kept open, `None` keeps it forever.
+    pub pool_idle_timeout: Option<Duration>,
+    /// TCP keep-alive interval of the pooled connections, `None` disables it.
+    pub tcp_keepalive: Option<Duration>,
+    /// Timeout of a single phonemization request, `None` waits forever.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            pool_max_idle_per_host: 32,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            timeout: Some(Duration::from_secs(60)),
+        }
+    }
+}
+
+/// Limits of the on-disk phonemization cache kept in the download folder.
+#[derive(Debug, Clone)]
+pub struct DiskCacheOptions {
+    /// Total size of the cached responses in bytes.
+    pub max_bytes: u64,
+    /// Number of cached responses.
+    pub max_entries: usize,
+}
+
+impl Default for DiskCacheOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: 256 * 1024 * 1024,
+            max_entries: 100_000,
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::models::responses;
+
+#[derive(Error, Debug)]
+pub enum PhonemeIdError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serde JSON error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("Invalid symbol table: {0}")]
+    Config(String),
+}
+
+/// Symbol to ID mapping of a TTS model.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    ids: HashMap<String, Vec<i64>>,
+    // longest key in chars, bounds the greedy match
+    longest: usize,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, symbol: &str, ids: Vec<i64>) {
+        self.longest = self.longest.max(symbol.chars().count());
+        self.ids.insert(symbol.to_string(), ids);
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<&[i64]> {
+        self.ids.get(symbol).map(Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Load a model's `config.json`: Piper's `phoneme_id_map` (`{"a": [14]}`)
+    /// or a Kokoro-style `vocab` (`{"a": 43}`).
+    pub fn load_config(path: &Path) -> Result<Self, PhonemeIdError> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse_config(&text)
+    }
+
+    pub fn parse_config(text: &str) -> Result<Self, PhonemeIdError> {
+        let config: Value = serde_json::from_str(text)?;
+        let (map, key) = match (config.get("phoneme_id_map"), config.get("vocab")) {
+            (Some(Value::Object(map)), _) => (map, "phoneme_id_map"),
+            (_, Some(Value::Object(map))) => (map, "vocab"),
+            _ => {
+                return Err(PhonemeIdError::Config(
+                    "neither phoneme_id_map nor vocab found".to_string(),
+                ))
+            }
+        };
+
+        let mut table = Self::new();
+        for (symbol, ids) in map {
+            let ids = match ids {
+                Value::Number(id) => id.as_i64().map(|id| vec![id]),
+                Value::Array(ids) => ids.iter().map(Value::as_i64).collect(),
+                _ => None,
+            };
+            let Some(ids) = ids else {
+                return Err(PhonemeIdError::Config(format!(
+                    "{}[{:?}] is not an integer or an array of integers",
+                    key, symbol
+                )));
+            };
+            table.insert(symbol, ids);
+        }
+        Ok(table)
+    }
+}
+
+/// A symbol of the input which the table has no IDs for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingSymbol {
+    /// Index into `PhonemizeSentenceResp::words`.
+    pub word: usize,
+    pub symbol: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhonemeIds {
+    pub ids: Vec<i64>,
+    /// Symbols left out of `ids`, in input order.
+    pub missing: Vec<MissingSymbol>,
+}
+
+/// Turns phonemized sentences into model input IDs.
+///
+/// Each word's `phonetic` is split greedily into the longest symbols of the
+/// table, which for Piper and Kokoro are single code points. `pre_punct` and
+/// `post_punct` are encoded around the word, the word separator between words.
+///
+/// # Examples
+///
+/// ```
+/// use rustruut::phoneme_ids::{PhonemeIdEncoder, SymbolTable};
+/// use rustruut::{PhonemizeSentenceResp, PhonemizeSentenceWord};
+///
+/// let table = SymbolTable::parse_config(
+///     r#"{"phoneme_id_map": {"_": [0], "^": [1], "$": [2], " ": [3], "h": [20], "i": [21], "!": [4]}}"#,
+/// )?;
+/// let resp = PhonemizeSentenceResp {
+///     words: vec![PhonemizeSentenceWord {
+///         phonetic: "hi".to_string(),
+///         post_punct: "!".to_string(),
+///         ..Default::default()
+///     }],
+///     ..Default::default()
+/// };
+/// let encoded = PhonemeIdEncoder::piper(table).encode(&resp);
+/// assert_eq!(encoded.ids, vec![1, 0, 20, 0, 21, 0, 4, 0, 2]);
+/// # Ok::<(), rustruut::phoneme_ids::PhonemeIdError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PhonemeIdEncoder {
+    table: SymbolTable,
+    pad: Vec<i64>,
+    bos: Vec<i64>,
+    eos: Vec<i64>,
+    word_separator: Vec<i64>,
+    intersperse_pad: bool,
+    punctuation: bool,
+}
+
+impl PhonemeIdEncoder {
+    /// Encoder without any special tokens.
+    pub fn new(table: SymbolTable) -> Self {
+        Self {
+            table,
+            pad: vec![],
+            bos: vec![],
+            eos: vec![],
+            word_separator: vec![],
+            intersperse_pad: false,
+            punctuation: true,
+        }
+    }
+
+    /// Piper conventions: `^` and `$` around the sentence, `_` after every
+    /// symbol and `" "` between words, as far as the table has them.
+    pub fn piper(table: SymbolTable) -> Self {
+        let special = |symbol: &str| table.get(symbol).map(<[i64]>::to_vec).unwrap_or_default();
+        let (pad, bos, eos, space) = (special("_"), special("^"), special("$"), special(" "));
+        Self::new(table)
+            .with_pad(pad)
+            .with_bos(bos)
+            .with_eos(eos)
+            .with_word_separator(space)
+            .with_intersperse_pad(true)
+    }
+
+    /// Kokoro conventions: ID `0` around the sentence and `" "` between words.
+    pub fn kokoro(table: SymbolTable) -> Self {
+        let space = table.get(" ").map(<[i64]>::to_vec).unwrap_or_default();
+        Self::new(table)
+            .with_bos(vec![0])
+            .with_eos(vec![0])
+            .with_word_separator(space)
+    }
+
+    pub fn with_pad(mut self, ids: Vec<i64>) -> Self {
+        self.pad = ids;
+        self
+    }
+
+    pub fn with_bos(mut self, ids: Vec<i64>) -> Self {
+        self.bos = ids;
+        self
+    }
+
+    pub fn with_eos(mut self, ids: Vec<i64>) -> Self {
+        self.eos = ids;
+        self
+    }
+
+    /// IDs written between two words, none when empty.
+    pub fn with_word_separator(mut self, ids: Vec<i64>) -> Self {
+        self.word_separator = ids;
+        self
+    }
+
+    /// Write the pad IDs after BOS and after every symbol and separator.
+    pub fn with_intersperse_pad(mut self, intersperse_pad: bool) -> Self {
+        self.intersperse_pad = intersperse_pad;
+        self
+    }
+
+    /// Whether `pre_punct` and `post_punct` are encoded, on by default.
+    pub fn with_punctuation(mut self, punctuation: bool) -> Self {
+        self.punctuation = punctuation;
+        self
+    }
+
+    pub fn table(&self) -> &SymbolTable {
+        &self.table
+    }
+
+    pub fn encode(&self, resp: &responses::PhonemizeSentence) -> PhonemeIds {
+        let mut out = PhonemeIds::default();
+        out.ids.extend(&self.bos);
+        if self.intersperse_pad {
+            out.ids.extend(&self.pad);
+        }
+
+        for (i, word) in resp.words.iter().enumerate() {
+            if i > 0 && !self.word_separator.is_empty() {
+                self.push(&mut out.ids, &self.word_separator);
+            }
+            if self.punctuation {
+                self.encode_text(&mut out, i, &word.pre_punct);
+            }
+            self.encode_text(&mut out, i, &word.phonetic);
+            if self.punctuation {
+                self.encode_text(&mut out, i, &word.post_punct);
+            }
+        }
+
+        out.ids.extend(&self.eos);
+        out
+    }
+
+    fn push(&self, ids: &mut Vec<i64>, symbol: &[i64]) {
+        ids.extend(symbol);
+        if self.intersperse_pad {
+            ids.extend(&self.pad);
+        }
+    }
+
+    fn encode_text(&self, out: &mut PhonemeIds, word: usize, text: &str) {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let start = chars[i].0;
+            let longest = self.table.longest.min(chars.len() - i).max(1);
+            let found = (1..=longest).rev().find_map(|n| {
+                let end = chars.get(i + n).map_or(text.len(), |(end, _)| *end);
+                self.table.get(&text[start..end]).map(|ids| (n, ids))
+            });
+            match found {
+                Some((n, ids)) => {
+                    self.push(&mut out.ids, ids);
+                    i += n;
+                }
+                None => {
+                    out.missing.push(MissingSymbol {
+                        word,
+                        symbol: chars[i].1.to_string(),
+                    });
+                    i += 1;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::di::DependencyInjection;
+use crate::interfaces::{Api, DictGetter, Folder, IpaFlavor, PolicyMaxWords, Version};
+use crate::models::requests::PhonemizeSentence as Req;
+use crate::models::responses::PhonemizeSentence as Resp;
+use crate::options::PhonemizerOptions;
+use crate::usecases::dict;
+use crate::usecases::pipeline::Pipeline;
+use crate::usecases::rustruut::RustruutError;
+use crate::usecases::rustruut_async::AsyncGoruut;
+use crate::usecases::word_cache::WordCacheStats;
+use std::collections::HashMap;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::Poll;
+
+/// Non-blocking Phonemizer API for tokio based services.
+///
+/// Downloading and starting the goruut executable as well as every request
+/// to goruut run on the async runtime. A crashed goruut process is respawned
+/// under `PhonemizerOptions::restart`. Requests are handled like by `Phonemizer`,
+/// with the same `PhonemizerOptions`; only the small entry files of the disk
+/// cache are read and written in place.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use rustruut::{AsyncPhonemizer, DependencyInjection, PhonemizeSentenceReq};
+/// let di: DependencyInjection = DependencyInjection::new();
+/// let p = AsyncPhonemizer::new(di).await;
+/// let req: PhonemizeSentenceReq = serde_json::from_str(r#"{"Language": "English", "Sentence": "hello"}"#)?;
+/// let _resp = p.sentence(req).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncPhonemizer<P, I, D, A, F, V>
+where
+    P: PolicyMaxWords,
+    I: IpaFlavor,
+    D: DictGetter,
+    A: Api,
+    F: Folder,
+    V: Version,
+{
+    // either a ready goruut or the stored error from the constructor
+    state: Result<AsyncGoruut<P, I, D, A, F, V>, RustruutError>,
+    pipeline: Pipeline,
+}
+
+impl<P, I, D, A, F, V> AsyncPhonemizer<P, I, D, A, F, V>
+where
+    P: PolicyMaxWords,
+    I: IpaFlavor,
+    D: DictGetter,
+    A: Api,
+    F: Folder,
+    V: Version,
+{
+    /// Construct from DI container.
+    pub async fn new(di: DependencyInjection<P, I, D, A, F, V>) -> Self
+    where
+        D: 'static,
+    {
+        Self::with_options(di, PhonemizerOptions::default()).await
+    }
+
+    /// Construct from DI container with explicit runtime options.
+    pub async fn with_options(
+        di: DependencyInjection<P, I, D, A, F, V>,
+        mut options: PhonemizerOptions,
+    ) -> Self
+    where
+        D: 'static,
+    {
+        let models = HashMap::new();
+        let maxwrds = di.policy.get_policy_max_words();
+        let version = di.version.get_version().map(|s| s.to_string());
+        let folder_dir = di.folder.get_download_dir().map(|s| s.to_string());
+        let api_path = di.api.get_api_path().to_string();
+
+        // Dictionaries provided by the DictGetter, applied along with the user lexicon
+        let (dict, mut lexicon) = (di.dict_getter.clone(), options.lexicon.take());
+        let (dictionaries, lexicon) = tokio::task::spawn_blocking(move || {
+            let loaded = dict::add_dictionaries(&dict, &mut lexicon);
+            (loaded, lexicon)
+        })
+        .await
+        .expect("loading the dictionaries panicked");
+        options.lexicon = lexicon;
+        let mut pipeline = Pipeline::new(maxwrds, di.ipa.get_ipa_flavors(), options);
+
+        let state = match dictionaries {
+            Ok(()) => {
+                AsyncGoruut::new(
+                    di,
+                    version.as_deref(),
+                    folder_dir.as_deref(),
+                    models,
+                    pipeline.options(),
+                )
+                .await
+            }
+            Err(err) => Err(err.into()),
+        };
+        if let Ok(g) = &state {
+            // Opening the cache scans its folder
+            let (engine_id, folder_dir) = (g.engine_id().map(str::to_string), folder_dir.clone());
+            pipeline = tokio::task::spawn_blocking(move || {
+                pipeline.open_cache(engine_id.as_deref(), &api_path, folder_dir.as_deref());
+                pipeline
+            })
+            .await
+            .expect("opening the disk cache panicked");
+        }
+        Self { state, pipeline }
+    }
+
+    /// Run phonemization on the sentence request and return a response.
+    pub async fn sentence(&self, req: Req) -> Result<Resp, RustruutError> {
+        let mut job = self.pipeline.begin(req)?;
+        let g = match &self.state {
+            Ok(g) => g,
+            Err(err) => {
+                return Err(RustruutError::Generic(format!(
+                    "goruut not available: {}",
+                    err
+                )))
+            }
+        };
+        while let Some(req) = job.request() {
+            job.respond(g.phonemize(req).await?);
+        }
+        job.finish()
+    }
+
+    /// Run phonemization on many sentence requests, `PhonemizerOptions::concurrency`
+    /// of them at a time. Results keep the input order and a failed item does not
+    /// abort the rest of the batch.
+    pub async fn sentences(&self, reqs: Vec<Req>) -> Vec<Result<Resp, RustruutError>> {
+        let total = reqs.len();
+        let workers = self.pipeline.options().concurrency.clamp(1, total.max(1));
+
+        // Workers pull the next request, so slow sentences don't stall a fixed slice
+        let queue = Mutex::new(reqs.into_iter().enumerate());
+        let results: Mutex<Vec<Option<Result<Resp, RustruutError>>>> =
+            Mutex::new((0..total).map(|_| None).collect());
+        let worker = || async {
+            loop {
+                let next = queue.lock().unwrap().next();
+                let Some((idx, req)) = next else {
+                    break;
+                };
+                let result = self.sentence(req).await;
+                results.lock().unwrap()[idx] = Some(result);
+            }
+        };
+
+        let mut running: Vec<Pin<Box<dyn Future<Output = ()> + '_>>> =
+            (0..workers).map(|_| Box::pin(worker()) as _).collect();
+        poll_fn(|cx| {
+            running.retain_mut(|w| w.as_mut().poll(cx).is_pending());
+            match running.is_empty() {
+                true => Poll::Ready(()),
+                false => Poll::Pending,
+            }
+        })
+        .await;
+        drop(running);
+
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(Option::unwrap)
+            .collect()
+    }
+
+    /// Hit/miss counters of the word cache, `None` unless
+    /// `PhonemizerOptions::word_cache_capacity` is set.
+    pub fn word_cache_stats(&self) -> Option<WordCacheStats> {
+        self.pipeline.word_cache_stats()
+    }
+}
@@ -1,13 +1,25 @@
 use super::interfaces::{Api, DictGetter, Folder, IpaFlavor, PolicyMaxWords, Version};
 use crate::di::DependencyInjection;
+use crate::export::{DictFormat, DictWriter, ExportReport};
 use crate::models::requests::PhonemizeSentence as Req;
 use crate::models::responses::PhonemizeSentence as Resp;
+use crate::options::PhonemizerOptions;
 use crate::usecases::phonemize::{PhonemizeUsecase, PhonemizeUsecaseImpl};
 use crate::usecases::rustruut::RustruutError;
+use crate::usecases::word_cache::WordCacheStats;
 
+#[cfg(feature = "async")]
+mod asynchronous;
 #[cfg(test)]
 mod test;
 
+#[cfg(feature = "async")]
+pub use asynchronous::AsyncPhonemizer;
+
+/// Requests of `Phonemizer::sentences` taken from the input at a time, a lazy
+/// input is never held in memory as a whole.
+const BATCH_SIZE: usize = 1024;
+
 /// Public-facing Phonemizer API.
 /// It owns a usecase internally and exposes a simple `sentence` method.
 pub struct Phonemizer<P, I, D, A, F, V>
@@ -37,8 +49,99 @@ where
         Self { usecase }
     }
 
+    /// Construct from DI container with explicit runtime options.
+    pub fn with_options(
+        di: DependencyInjection<P, I, D, A, F, V>,
+        options: PhonemizerOptions,
+    ) -> Self {
+        let usecase = PhonemizeUsecaseImpl::with_options(di, options);
+        Self { usecase }
+    }
+
     /// Run phonemization on the sentence request and return a response.
     pub fn sentence(&self, req: Req) -> Result<Resp, RustruutError> {
         self.usecase.sentence(req)
     }
+
+    /// Run phonemization on many sentence requests, `PhonemizerOptions::concurrency`
+    /// of them at a time. Results keep the input order and a failed item does not
+    /// abort the rest of the batch.
+    pub fn sentences(
+        &self,
+        reqs: impl IntoIterator<Item = Req>,
+    ) -> Vec<Result<Resp, RustruutError>> {
+        let mut reqs = reqs.into_iter();
+        let mut results = Vec::new();
+        loop {
+            let batch: Vec<Req> = reqs.by_ref().take(BATCH_SIZE).collect();
+            if batch.is_empty() {
+                return results;
+            }
+            results.extend(self.usecase.sentences(batch));
+        }
+    }
+
+    /// Hit/miss counters of the word cache, `None` unless
+    /// `PhonemizerOptions::word_cache_capacity` is set.
+    pub fn word_cache_stats(&self) -> Option<WordCacheStats> {
+        self.usecase.word_cache_stats()
+    }
+
+    /// Phonemize every word of `words` on its own and write a pronunciation
+    /// dictionary in the given format to `out`.
+    ///
+    /// Words are phonemized in batches like `sentences`, duplicates are exported
+    /// once. Words which fail are reported and left out, only write errors abort.
+    /// A language the format cannot write, see `DictFormat::supports`, fails
+    /// before any word is phonemized.
+    pub fn export_dictionary<'w, W: std::io::Write>(
+        &self,
+        language: &str,
+        words: impl IntoIterator<Item = &'w str>,
+        format: DictFormat,
+        out: W,
+    ) -> Result<ExportReport, RustruutError> {
+        if !format.supports(language) {
+            return Err(RustruutError::Generic(format!(
+                "{:?} dictionaries cannot hold pronunciations of \"{}\", only English",
+                format, language
+            )));
+        }
+        let mut seen = std::collections::HashSet::new();
+        let words: Vec<&str> = words
+            .into_iter()
+            .map(str::trim)
+            .filter(|w| !w.is_empty() && seen.insert(*w))
+            .collect();
+        let reqs = words.iter().map(|word| Req {
+            ipa_flavors: vec![],
+            language: language.to_string(),
+            languages: vec![],
+            sentence: word.to_string(),
+            is_reverse: false,
+            split_sentences: false,
+        });
+
+        let mut writer = DictWriter::new(out, format);
+        let mut report = ExportReport::default();
+        for (word, result) in words.iter().zip(self.sentences(reqs)) {
+            match result {
+                Ok(resp) if !resp.words.is_empty() => {
+                    let phonetic: Vec<&str> =
+                        resp.words.iter().map(|w| w.phonetic.as_str()).collect();
+                    match writer.write(word, &phonetic.join(" ")) {
+                        Ok(()) => report.written += 1,
+                        Err(RustruutError::Io(e)) => return Err(e.into()),
+                        Err(e) => report.failed.push((word.to_string(), e)),
+                    }
+                }
+                Ok(_) => report.failed.push((
+                    word.to_string(),
+                    RustruutError::Generic("no pronunciation returned".to_string()),
+                )),
+                Err(e) => report.failed.push((word.to_string(), e)),
+            }
+        }
+        Ok(report)
+    }
 }
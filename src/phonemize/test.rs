@@ -3,6 +3,8 @@ mod tests {
     use crate::di::DependencyInjection;
     use crate::models::requests::PhonemizeSentence as Req;
     use crate::models::responses::PhonemizeSentence as Resp;
+    use crate::test_util::MockServer;
+    use crate::usecases::rustruut::RustruutError;
     use crate::Phonemizer;
 
     /// Convert a PhonemizeSentence into a human-readable string with punctuation.
@@ -16,13 +18,14 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "downloads goruut and its Hebrew model from GitHub"]
     fn test_hebrew_phonemization() -> Result<(), Box<dyn std::error::Error>> {
         let di = DependencyInjection::with_parts(
             crate::di::default_impls::DummyPolicy,
             crate::di::default_impls::DummyIpaFlavor,
             crate::di::default_impls::DummyDict,
             crate::di::default_impls::DummyApi,
-            crate::di::custom_impls::CustomFolder::default(),
+            crate::di::custom_impls::CustomFolder,
             crate::di::custom_impls::CustomVersion::new("v0.7.0"),
         );
         let p = Phonemizer::new(di);
@@ -46,6 +49,35 @@ mod tests {
     }
 
     #[test]
+    fn smoke_test_mock_server() -> Result<(), Box<dyn std::error::Error>> {
+        let server = MockServer::lexicon([("hello", "həloʊ"), ("world", "wɝld")]);
+        let di = DependencyInjection::with_parts(
+            crate::di::default_impls::DummyPolicy,
+            crate::di::default_impls::DummyIpaFlavor,
+            crate::di::default_impls::DummyDict,
+            crate::di::custom_impls::CustomApi::new(&server.url),
+            crate::di::default_impls::DummyFolder,
+            crate::di::default_impls::DummyVersion,
+        );
+        let p = Phonemizer::new(di);
+
+        let req = Req {
+            ipa_flavors: vec![],
+            language: "English".to_string(),
+            languages: vec![],
+            sentence: "Hello world!".to_string(),
+            is_reverse: false,
+            split_sentences: false,
+        };
+
+        let res = p.sentence(req)?;
+        assert_eq!(render_response_with_punct(&res), "həloʊ wɝld!");
+        assert_eq!(server.requests(), 1);
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "downloads goruut from GitHub"]
     fn smoke_test_default_di() -> Result<(), Box<dyn std::error::Error>> {
         let di: DependencyInjection = DependencyInjection::new();
         let p = Phonemizer::new(di);
@@ -78,11 +110,11 @@ mod tests {
 
         let di = DependencyInjection::with_parts(
             TwoWordPolicy,
-            crate::di::default_impls::DummyIpaFlavor::default(),
-            crate::di::default_impls::DummyDict::default(),
-            crate::di::default_impls::DummyApi::default(),
-            crate::di::default_impls::DummyFolder::default(),
-            crate::di::default_impls::DummyVersion::default(),
+            crate::di::default_impls::DummyIpaFlavor,
+            crate::di::default_impls::DummyDict,
+            crate::di::default_impls::DummyApi,
+            crate::di::default_impls::DummyFolder,
+            crate::di::default_impls::DummyVersion,
         );
 
         let p = Phonemizer::new(di);
@@ -95,9 +127,13 @@ mod tests {
             split_sentences: false,
         };
 
-        let res = p.sentence(req)?;
-        assert!(res.error_word_limit_exceeded);
-        assert_eq!(res.words.len(), 0);
+        match p.sentence(req) {
+            Err(RustruutError::WordLimitExceeded { limit, actual }) => {
+                assert_eq!(limit, 2);
+                assert_eq!(actual, 3);
+            }
+            other => panic!("expected WordLimitExceeded, got {:?}", other),
+        }
         Ok(())
     }
 
@@ -0,0 +1,268 @@
+use std::fmt;
+
+use crate::ipa::{tokenize, Phoneme, Stress};
+use crate::models::responses;
+use crate::usecases::languages::{Language, LanguageError};
+
+/// One syllable of a pronunciation.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Syllable {
+    pub onset: Vec<Phoneme>,
+    /// Vowel, diphthong or syllabic consonant.
+    pub nucleus: Vec<Phoneme>,
+    pub coda: Vec<Phoneme>,
+    pub stress: Stress,
+}
+
+impl Syllable {
+    pub fn phonemes(&self) -> impl Iterator<Item = &Phoneme> {
+        self.onset.iter().chain(&self.nucleus).chain(&self.coda)
+    }
+}
+
+impl fmt::Display for Syllable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.stress {
+            Stress::None => {}
+            Stress::Primary => f.write_str("ˈ")?,
+            Stress::Secondary => f.write_str("ˌ")?,
+        }
+        for phoneme in self.phonemes() {
+            f.write_str(&phoneme.symbol())?;
+        }
+        Ok(())
+    }
+}
+
+/// Phonotactics the syllabifier needs from a language.
+#[derive(Debug, Clone, Copy)]
+struct Rules {
+    /// Vowel pairs written without a non-syllabic mark which form one nucleus.
+    diphthongs: &'static [(&'static str, &'static str)],
+    /// Sibilants which may precede a stop or nasal at the start of an onset.
+    sibilants: &'static [&'static str],
+    /// Least sonority of the second consonant of a cluster.
+    cluster_sonority: u8,
+    /// Two-consonant onsets ruled out although sonority rises.
+    banned: &'static [(&'static str, &'static str)],
+    /// Consonants which never start a syllable.
+    no_onset: &'static [&'static str],
+}
+
+const GENERIC: Rules = Rules {
+    diphthongs: &[],
+    sibilants: &[],
+    cluster_sonority: 0,
+    banned: &[],
+    no_onset: &[],
+};
+
+const ENGLISH: Rules = Rules {
+    diphthongs: &[
+        ("a", "ɪ"),
+        ("a", "ʊ"),
+        ("e", "ɪ"),
+        ("o", "ʊ"),
+        ("ɔ", "ɪ"),
+        ("ə", "ʊ"),
+        ("ɪ", "ə"),
+        ("e", "ə"),
+        ("ɛ", "ə"),
+        ("ʊ", "ə"),
+    ],
+    sibilants: &["s"],
+    cluster_sonority: LIQUID,
+    banned: &[("t", "l"), ("d", "l"), ("θ", "l")],
+    no_onset: &["ŋ"],
+};
+
+const GERMAN: Rules = Rules {
+    diphthongs: &[("a", "ɪ"), ("a", "ʊ"), ("ɔ", "ʏ"), ("ɔ", "ɪ")],
+    sibilants: &["ʃ", "s"],
+    cluster_sonority: NASAL,
+    banned: &[("t", "l"), ("d", "l")],
+    no_onset: &["ŋ"],
+};
+
+const STOP: u8 = 1;
+const FRICATIVE: u8 = 2;
+const NASAL: u8 = 3;
+const LIQUID: u8 = 4;
+const GLIDE: u8 = 5;
+const VOWEL: u8 = 6;
+
+fn sonority(phoneme: &Phoneme) -> u8 {
+    if phoneme.is_vowel() {
+        // a non-syllabic vowel outside a nucleus behaves like a glide
+        return if phoneme.is_syllabic() { VOWEL } else { GLIDE };
+    }
+    let Some(c) = phoneme.base.chars().next() else {
+        return STOP;
+    };
+    match c {
+        'j' | 'w' | 'ɥ' | 'ɰ' | 'ʍ' | 'ʋ' => GLIDE,
+        'l' | 'r' | 'ɹ' | 'ɾ' | 'ɫ' | 'ʎ' | 'ɭ' | 'ʀ' | 'ɽ' | 'ɻ' | 'ʟ' | 'ɺ' => LIQUID,
+        'm' | 'n' | 'ŋ' | 'ɲ' | 'ɳ' | 'ɴ' | 'ɱ' => NASAL,
+        'p' | 'b' | 't' | 'd' | 'k' | 'g' | 'ɡ' | 'q' | 'c' | 'ɟ' | 'ʔ' | 'ʈ' | 'ɖ' | 'ɢ' => {
+            // affricates start with a stop but pattern with fricatives
+            if phoneme.base.chars().count() > 1 {
+                FRICATIVE
+            } else {
+                STOP
+            }
+        }
+        _ => FRICATIVE,
+    }
+}
+
+/// Splits pronunciations into syllables.
+///
+/// Syllable breaks (`.`) and stress marks in front of consonants are kept as
+/// boundaries. Elsewhere consonants between two nuclei go to the following
+/// syllable as far as they form a valid onset of the language (maximal onset).
+///
+/// # Examples
+///
+/// ```
+/// use rustruut::syllable::Syllabifier;
+/// let syllabifier = Syllabifier::new("English")?;
+/// let syllables: Vec<String> = syllabifier
+///     .syllabify("ɪkˈstɹiːm")
+///     .iter()
+///     .map(ToString::to_string)
+///     .collect();
+/// assert_eq!(syllables, vec!["ɪk", "ˈstɹiːm"]);
+/// # Ok::<(), rustruut::LanguageError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Syllabifier {
+    rules: Rules,
+}
+
+impl Syllabifier {
+    pub fn new(language: &str) -> Result<Self, LanguageError> {
+        let language = Language::parse(language)?;
+        let rules = match language.name() {
+            name if name.starts_with("English") => ENGLISH,
+            name if name.starts_with("German") => GERMAN,
+            _ => GENERIC,
+        };
+        Ok(Self { rules })
+    }
+
+    /// Syllables of every word of a response, in word order.
+    pub fn sentence(&self, resp: &responses::PhonemizeSentence) -> Vec<Vec<Syllable>> {
+        resp.words.iter().map(|word| self.word(word)).collect()
+    }
+
+    pub fn word(&self, word: &responses::PhonemizeSentenceWord) -> Vec<Syllable> {
+        self.syllabify(&word.phonetic)
+    }
+
+    pub fn syllabify(&self, ipa: &str) -> Vec<Syllable> {
+        // phonemes and whether a boundary is marked before each one
+        let mut phonemes: Vec<Phoneme> = Vec::new();
+        let mut marked: Vec<bool> = Vec::new();
+        for chunk in ipa.split(|c: char| c == '.' || c.is_whitespace()) {
+            for (i, phoneme) in tokenize(chunk).into_iter().enumerate() {
+                let stressed_onset = phoneme.stress != Stress::None && !phoneme.is_syllabic();
+                marked.push((i == 0 && !phonemes.is_empty()) || stressed_onset);
+                phonemes.push(phoneme);
+            }
+        }
+        if phonemes.is_empty() {
+            return vec![];
+        }
+
+        let nuclei = self.nuclei(&phonemes, &marked);
+        let mut syllables = Vec::with_capacity(nuclei.len());
+        let mut start = 0;
+        for (k, &(first, last)) in nuclei.iter().enumerate() {
+            let end = match nuclei.get(k + 1) {
+                Some(&(next, _)) => {
+                    last + self.onset_start(&phonemes[last..next], &marked[last..=next])
+                }
+                None => phonemes.len(),
+            };
+            let stress = phonemes[start..end]
+                .iter()
+                .map(|p| p.stress)
+                .find(|s| *s != Stress::None)
+                .unwrap_or_default();
+            syllables.push(Syllable {
+                onset: phonemes[start..first].to_vec(),
+                nucleus: phonemes[first..last].to_vec(),
+                coda: phonemes[last..end].to_vec(),
+                stress,
+            });
+            start = end;
+        }
+        syllables
+    }
+
+    /// Half-open ranges of the nuclei, the most sonorous phoneme when there is no vowel.
+    fn nuclei(&self, phonemes: &[Phoneme], marked: &[bool]) -> Vec<(usize, usize)> {
+        let mut nuclei: Vec<(usize, usize)> = Vec::new();
+        for (i, phoneme) in phonemes.iter().enumerate() {
+            let extends = nuclei.last().is_some_and(|&(_, last)| {
+                last == i && !marked[i] && phoneme.stress == Stress::None && {
+                    let prev = &phonemes[i - 1];
+                    (phoneme.is_vowel() && !phoneme.is_syllabic())
+                        || self
+                            .rules
+                            .diphthongs
+                            .contains(&(prev.base.as_str(), phoneme.base.as_str()))
+                }
+            });
+            if extends {
+                nuclei.last_mut().unwrap().1 = i + 1;
+            } else if phoneme.is_syllabic() {
+                nuclei.push((i, i + 1));
+            }
+        }
+        if nuclei.is_empty() {
+            let peak = (0..phonemes.len())
+                .max_by_key(|&i| (sonority(&phonemes[i]), std::cmp::Reverse(i)))
+                .unwrap_or_default();
+            nuclei.push((peak, peak + 1));
+        }
+        nuclei
+    }
+
+    /// Where the onset of the next syllable starts within the consonants between
+    /// two nuclei, `marked` also covers the next nucleus.
+    fn onset_start(&self, consonants: &[Phoneme], marked: &[bool]) -> usize {
+        // an explicit break wins
+        if let Some(i) = (0..marked.len()).rev().find(|&i| marked[i]) {
+            return i;
+        }
+        (0..=consonants.len())
+            .find(|&i| self.is_onset(&consonants[i..]))
+            .unwrap_or(consonants.len())
+    }
+
+    fn is_onset(&self, consonants: &[Phoneme]) -> bool {
+        let rules = &self.rules;
+        if consonants
+            .iter()
+            .any(|c| rules.no_onset.contains(&c.base.as_str()))
+        {
+            return false;
+        }
+        match consonants {
+            [] | [_] => true,
+            [first, rest @ ..]
+                if rules.sibilants.contains(&first.base.as_str())
+                    && matches!(sonority(&rest[0]), STOP | NASAL) =>
+            {
+                self.is_onset(rest)
+            }
+            _ => consonants.windows(2).all(|pair| {
+                let (a, b) = (&pair[0], &pair[1]);
+                sonority(a) < sonority(b)
+                    && sonority(b) >= rules.cluster_sonority
+                    && !rules.banned.contains(&(a.base.as_str(), b.base.as_str()))
+            }),
+        }
+    }
+}
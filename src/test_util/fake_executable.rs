@@ -0,0 +1,139 @@
+use std::collections::HashSet;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::Value;
+use tempfile::TempDir;
+
+use super::mock_server::{lexicon_handler, MockServer};
+
+/// Shell script standing in for the goruut executable.
+///
+/// It is started like goruut, `<path> --configfile <config>`, and every launch
+/// is logged so tests can check spawns and respawns. `serve_*` scripts answer
+/// the API with a `MockServer` on the port of the config file and print
+/// `Serving...` once it listens. Unix only.
+pub struct FakeExecutable {
+    dir: TempDir,
+    path: PathBuf,
+    stop: Arc<AtomicBool>,
+}
+
+impl FakeExecutable {
+    /// Run `body` as `/bin/sh` script, `$DIR` is a scratch directory.
+    pub fn script(body: &str) -> Self {
+        let dir = tempfile::tempdir().expect("create a temp dir");
+        let path = dir.path().join("goruut");
+        let script = format!(
+            "#!/bin/sh\nDIR='{}'\necho \"$@\" >> \"$DIR/launches\"\n{}\n",
+            dir.path().display(),
+            body
+        );
+        fs::write(&path, script).expect("write the fake executable");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .expect("make the fake executable runnable");
+        Self {
+            dir,
+            path,
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Reports `Serving...` but never answers requests.
+    pub fn idle() -> Self {
+        Self::script("echo 'Serving...' >&2\nexec sleep 3600")
+    }
+
+    /// Exits with `code` before reporting it serves.
+    pub fn failing(code: i32) -> Self {
+        Self::script(&format!("echo 'no model' >&2\nexit {}", code))
+    }
+
+    /// Serves `handler` like goruut would, see `MockServer`.
+    pub fn serve<H>(handler: H) -> Self
+    where
+        H: Fn(&Value) -> Value + Send + Sync + Clone + 'static,
+    {
+        // The script announces the configured port and waits for the server
+        let fake = Self::script(concat!(
+            "port=$(sed -n 's/.*\"Port\": *\"\\([0-9]*\\)\".*/\\1/p' \"$2\")\n",
+            "touch \"$DIR/port.$port\"\n",
+            "while [ ! -e \"$DIR/ready.$port\" ]; do\n",
+            "  [ -e \"$DIR/failed.$port\" ] && exit 1\n",
+            "  sleep 0.01\n",
+            "done\n",
+            "echo 'Serving...' >&2\n",
+            "exec sleep 3600",
+        ));
+
+        let dir = fake.dir.path().to_path_buf();
+        let stop = fake.stop.clone();
+        thread::spawn(move || {
+            let mut servers = Vec::new();
+            let mut seen = HashSet::new();
+            while !stop.load(Ordering::SeqCst) {
+                for port in announced_ports(&dir) {
+                    if !seen.insert(port) {
+                        continue;
+                    }
+                    let marker = match MockServer::bind(("127.0.0.1", port), handler.clone()) {
+                        Ok(server) => {
+                            servers.push(server);
+                            "ready"
+                        }
+                        Err(_) => "failed",
+                    };
+                    let _ = fs::write(dir.join(format!("{}.{}", marker, port)), "");
+                }
+                thread::sleep(Duration::from_millis(5));
+            }
+        });
+        fake
+    }
+
+    /// Serves a scripted word list, see `lexicon_handler`.
+    pub fn serve_lexicon<I, W, P>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (W, P)>,
+        W: Into<String>,
+        P: Into<String>,
+    {
+        let handler = Arc::new(lexicon_handler(entries));
+        Self::serve(move |body| handler(body))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Arguments of every launch so far, one entry per launch.
+    pub fn launches(&self) -> Vec<String> {
+        fs::read_to_string(self.dir.path().join("launches"))
+            .map(|log| log.lines().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for FakeExecutable {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+fn announced_ports(dir: &Path) -> Vec<u16> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_prefix("port.")?.parse().ok()
+        })
+        .collect()
+}
@@ -0,0 +1,162 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use sha2::{Digest, Sha256};
+
+use crate::usecases::executable::Executable;
+use crate::usecases::platform::Platform;
+
+/// Misbehaviour of a `FileServer`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Faults {
+    /// Close every connection after sending this many bytes of the body.
+    pub drop_after: Option<usize>,
+    /// Answer range requests with the whole file, like servers without range support.
+    pub ignore_range: bool,
+}
+
+/// In-process HTTP server for testing executable downloads.
+///
+/// Answers `GET` of any path with `content`, honouring `Range: bytes=<start>-`
+/// with `206 Partial Content`. Every response closes its connection.
+///
+/// # Examples
+///
+/// ```
+/// use rustruut::test_util::{Faults, FileServer};
+///
+/// let server = FileServer::with_faults(
+///     b"0123456789".to_vec(),
+///     Faults {
+///         drop_after: Some(4),
+///         ..Default::default()
+///     },
+/// );
+/// assert!(server.url.starts_with("http://127.0.0.1:"));
+/// assert!(server.ranges().is_empty());
+/// ```
+pub struct FileServer {
+    pub url: String,
+    ranges: Arc<Mutex<Vec<Option<u64>>>>,
+}
+
+impl FileServer {
+    /// Serve `content` on a free local port.
+    pub fn start(content: Vec<u8>) -> Self {
+        Self::with_faults(content, Faults::default())
+    }
+
+    pub fn with_faults(content: Vec<u8>, faults: Faults) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind a local port");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let content: Arc<[u8]> = content.into();
+
+        let r = ranges.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (content, r) = (content.clone(), r.clone());
+                thread::spawn(move || {
+                    let _ = serve(stream, &content, faults, &r);
+                });
+            }
+        });
+
+        Self { url, ranges }
+    }
+
+    /// Start offset of the `Range` header of every request so far, `None` without one.
+    pub fn ranges(&self) -> Vec<Option<u64>> {
+        self.ranges.lock().unwrap().clone()
+    }
+}
+
+/// `size` bytes standing in for an executable, a pattern that does not repeat
+/// at common buffer sizes.
+pub fn executable_content(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+/// Executable of the current platform downloading as `content` from `servers`.
+pub fn executable_for(content: &[u8], servers: Vec<String>) -> Executable {
+    let platform = Platform::new().expect("a supported platform");
+    Executable {
+        size: content.len() as u64,
+        sha256: format!("{:x}", Sha256::digest(content)),
+        architecture: platform.architecture,
+        os: platform.os,
+        servers,
+    }
+}
+
+fn serve(
+    stream: TcpStream,
+    content: &[u8],
+    faults: Faults,
+    ranges: &Mutex<Vec<Option<u64>>>,
+) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let get = line.starts_with("GET ");
+
+    let mut range = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("range") {
+                range = value
+                    .trim()
+                    .strip_prefix("bytes=")
+                    .and_then(|v| v.strip_suffix('-'))
+                    .and_then(|v| v.parse::<u64>().ok());
+            }
+        }
+    }
+    if !get {
+        writer.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")?;
+        return Ok(());
+    }
+    ranges.lock().unwrap().push(range);
+
+    let len = content.len();
+    let (head, body) = match range.map(|start| start as usize) {
+        Some(start) if !faults.ignore_range && start < len => (
+            format!(
+                "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\n",
+                start,
+                len - 1,
+                len,
+                len - start
+            ),
+            &content[start..],
+        ),
+        Some(_) if !faults.ignore_range => (
+            format!(
+                "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\n",
+                len
+            ),
+            &content[..0],
+        ),
+        _ => (
+            format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n", len),
+            content,
+        ),
+    };
+    writer.write_all(head.as_bytes())?;
+    writer.write_all(b"Connection: close\r\n\r\n")?;
+    let sent = faults.drop_after.map_or(body.len(), |n| n.min(body.len()));
+    writer.write_all(&body[..sent])?;
+    writer.flush()?;
+    writer.shutdown(Shutdown::Both)
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::{json, Value};
+
+/// Path goruut serves phonemization on.
+pub const SENTENCE_PATH: &str = "/tts/phonemize/sentence";
+
+type Handler = dyn Fn(&Value) -> Value + Send + Sync;
+
+/// In-process stand-in for the goruut HTTP API.
+///
+/// A minimal HTTP/1.1 server with keep-alive which answers
+/// `POST /tts/phonemize/sentence` with `handler(body)` and anything else with
+/// 404. Point `CustomApi` at `url` to run the whole stack without goruut.
+///
+/// # Examples
+///
+/// ```
+/// use rustruut::test_util::MockServer;
+/// use rustruut::{di, DependencyInjection, Phonemizer, PhonemizeSentenceReq};
+///
+/// let server = MockServer::lexicon([("hello", "həloʊ"), ("world", "wɝld")]);
+/// let di = DependencyInjection::with_parts(
+///     di::default_impls::DummyPolicy,
+///     di::default_impls::DummyIpaFlavor,
+///     di::default_impls::DummyDict,
+///     di::custom_impls::CustomApi::new(&server.url),
+///     di::default_impls::DummyFolder,
+///     di::default_impls::DummyVersion,
+/// );
+/// let resp = Phonemizer::new(di).sentence(PhonemizeSentenceReq {
+///     ipa_flavors: vec![],
+///     language: "English".to_string(),
+///     languages: vec![],
+///     sentence: "Hello, world!".to_string(),
+///     is_reverse: false,
+///     split_sentences: false,
+/// })?;
+/// assert_eq!(resp.words[0].phonetic, "həloʊ");
+/// assert_eq!(resp.words[1].post_punct, "!");
+/// # Ok::<(), rustruut::usecases::rustruut::RustruutError>(())
+/// ```
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<AtomicUsize>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    /// Serve on a free local port.
+    pub fn start<H>(handler: H) -> Self
+    where
+        H: Fn(&Value) -> Value + Send + Sync + 'static,
+    {
+        Self::bind("127.0.0.1:0", handler).expect("bind a local port")
+    }
+
+    /// Serve on the given address, e.g. the port from a goruut config file.
+    pub fn bind<H>(addr: impl ToSocketAddrs, handler: H) -> io::Result<Self>
+    where
+        H: Fn(&Value) -> Value + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind(addr)?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let handler: Arc<Handler> = Arc::new(handler);
+        let requests = Arc::new(AtomicUsize::new(0));
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let (r, c) = (requests.clone(), connections.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                c.fetch_add(1, Ordering::SeqCst);
+                let handler = handler.clone();
+                let r = r.clone();
+                thread::spawn(move || serve(stream, &*handler, &r));
+            }
+        });
+
+        Ok(Self {
+            url,
+            requests,
+            connections,
+        })
+    }
+
+    /// Server which splits the sentence on whitespace and echoes every word as its phonetic.
+    pub fn echo() -> Self {
+        Self::start(|body| {
+            let sentence = body["Sentence"].as_str().unwrap_or_default();
+            let words: Vec<&str> = sentence.split_whitespace().collect();
+            let n = words.len();
+            let words: Vec<Value> = words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| {
+                    json!({
+                        "CleanWord": w,
+                        "Phonetic": w,
+                        "IsFirst": i == 0,
+                        "IsLast": i + 1 == n,
+                    })
+                })
+                .collect();
+            json!({ "Words": words })
+        })
+    }
+
+    /// Server answering from a scripted word list, see `lexicon_handler`.
+    pub fn lexicon<I, W, P>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (W, P)>,
+        W: Into<String>,
+        P: Into<String>,
+    {
+        Self::start(lexicon_handler(entries))
+    }
+
+    /// Number of phonemize requests answered so far.
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    /// Number of TCP connections accepted so far.
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+/// Handler which phonemizes like goruut from a fixed word list.
+///
+/// The sentence is split on whitespace, ASCII and common Unicode punctuation
+/// goes to `PrePunct`/`PostPunct`, words are looked up case-insensitively and
+/// reverse requests map pronunciations back to words. Unknown words are echoed.
+/// With `SplitSentences` a word after `.`, `!` or `?` starts a new sentence.
+pub fn lexicon_handler<I, W, P>(entries: I) -> impl Fn(&Value) -> Value + Send + Sync + 'static
+where
+    I: IntoIterator<Item = (W, P)>,
+    W: Into<String>,
+    P: Into<String>,
+{
+    let mut words: HashMap<String, String> = HashMap::new();
+    let mut spellings: HashMap<String, String> = HashMap::new();
+    for (word, phonetic) in entries {
+        let (word, phonetic) = (word.into(), phonetic.into());
+        spellings.insert(phonetic.clone(), word.clone());
+        words.insert(word.to_lowercase(), phonetic);
+    }
+
+    move |body| {
+        let sentence = body["Sentence"].as_str().unwrap_or_default();
+        let reverse = body["IsReverse"].as_bool().unwrap_or(false);
+        let split = body["SplitSentences"].as_bool().unwrap_or(false);
+        let is_punct = |c: char| c.is_ascii_punctuation() || "¿¡«»„“”‘’…—–".contains(c);
+        let ends_sentence = |post: &str| post.contains(['.', '!', '?']);
+
+        let tokens: Vec<&str> = sentence.split_whitespace().collect();
+        let mut out = Vec::with_capacity(tokens.len());
+        let mut prev_post = "";
+        for (i, token) in tokens.iter().enumerate() {
+            let rest = token.trim_start_matches(is_punct);
+            let pre = &token[..token.len() - rest.len()];
+            let clean = rest.trim_end_matches(is_punct);
+            let post = &rest[clean.len()..];
+
+            let phonetic = if reverse {
+                spellings.get(clean)
+            } else {
+                words.get(&clean.to_lowercase())
+            };
+            out.push(json!({
+                "CleanWord": clean,
+                "Phonetic": phonetic.map_or(clean, String::as_str),
+                "PrePunct": pre,
+                "PostPunct": post,
+                "IsFirst": i == 0 || (split && ends_sentence(prev_post)),
+                "IsLast": i + 1 == tokens.len() || (split && ends_sentence(post)),
+            }));
+            prev_post = post;
+        }
+        json!({ "Words": out })
+    }
+}
+
+fn serve(stream: TcpStream, handler: &Handler, requests: &AtomicUsize) {
+    let _ = stream.set_nodelay(true);
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut content_length = 0;
+        let mut line = String::new();
+        // request line, EOF ends the keep-alive connection
+        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+            return;
+        }
+        let mut parts = line.split_whitespace();
+        let known = parts.next() == Some("POST") && parts.next() == Some(SENTENCE_PATH);
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        if reader.read_exact(&mut body).is_err() {
+            return;
+        }
+
+        let message = if known {
+            requests.fetch_add(1, Ordering::SeqCst);
+            let request: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+            let response = handler(&request).to_string();
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                response.len(),
+                response
+            )
+        } else {
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string()
+        };
+        if writer.write_all(message.as_bytes()).is_err() {
+            return;
+        }
+    }
+}
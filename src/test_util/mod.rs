@@ -0,0 +1,29 @@
+//! Offline stand-ins for goruut and its download servers, for tests of rustruut
+//! and of crates using it.
+
+use std::time::Duration;
+
+use crate::RestartPolicy;
+
+#[cfg(unix)]
+mod fake_executable;
+mod file_server;
+mod mock_server;
+mod phonemizer;
+
+#[cfg(unix)]
+pub use fake_executable::FakeExecutable;
+pub use file_server::{executable_content, executable_for, Faults, FileServer};
+pub use mock_server::{lexicon_handler, MockServer, SENTENCE_PATH};
+#[cfg(feature = "async")]
+pub use phonemizer::{mock_async_phonemizer, MockAsyncPhonemizer};
+pub use phonemizer::{mock_di, mock_phonemizer, request, MockDi, MockPhonemizer};
+
+/// Restart policy backing off for 10 to 50 ms, keeps tests of respawns fast.
+pub fn fast_restart_policy(max_restarts: usize) -> RestartPolicy {
+    RestartPolicy {
+        max_restarts,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(50),
+    }
+}
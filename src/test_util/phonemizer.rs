@@ -0,0 +1,69 @@
+use crate::di::custom_impls::CustomApi;
+use crate::di::default_impls::{DummyDict, DummyFolder, DummyIpaFlavor, DummyPolicy, DummyVersion};
+use crate::models::requests::PhonemizeSentence;
+use crate::{DependencyInjection, Phonemizer, PhonemizerOptions};
+
+use super::mock_server::MockServer;
+
+/// DI container of a phonemizer talking to a `MockServer`, see `mock_di`.
+pub type MockDi = DependencyInjection<
+    DummyPolicy,
+    DummyIpaFlavor,
+    DummyDict,
+    CustomApi,
+    DummyFolder,
+    DummyVersion,
+>;
+
+/// `Phonemizer` talking to a `MockServer`, see `mock_phonemizer`.
+pub type MockPhonemizer =
+    Phonemizer<DummyPolicy, DummyIpaFlavor, DummyDict, CustomApi, DummyFolder, DummyVersion>;
+
+/// `AsyncPhonemizer` talking to a `MockServer`, see `mock_async_phonemizer`.
+#[cfg(feature = "async")]
+pub type MockAsyncPhonemizer = crate::AsyncPhonemizer<
+    DummyPolicy,
+    DummyIpaFlavor,
+    DummyDict,
+    CustomApi,
+    DummyFolder,
+    DummyVersion,
+>;
+
+/// English request for `sentence`, without flavors, reversal or sentence splitting.
+pub fn request(sentence: &str) -> PhonemizeSentence {
+    PhonemizeSentence {
+        ipa_flavors: vec![],
+        language: "English".to_string(),
+        languages: vec![],
+        sentence: sentence.to_string(),
+        is_reverse: false,
+        split_sentences: false,
+    }
+}
+
+/// DI container using the API of `server`, every other part is the dummy one.
+pub fn mock_di(server: &MockServer) -> MockDi {
+    DependencyInjection::with_parts(
+        DummyPolicy,
+        DummyIpaFlavor,
+        DummyDict,
+        CustomApi::new(&server.url),
+        DummyFolder,
+        DummyVersion,
+    )
+}
+
+/// Phonemizer using the API of `server`.
+pub fn mock_phonemizer(server: &MockServer, options: PhonemizerOptions) -> MockPhonemizer {
+    Phonemizer::with_options(mock_di(server), options)
+}
+
+/// Async phonemizer using the API of `server`.
+#[cfg(feature = "async")]
+pub async fn mock_async_phonemizer(
+    server: &MockServer,
+    options: PhonemizerOptions,
+) -> MockAsyncPhonemizer {
+    crate::AsyncPhonemizer::with_options(mock_di(server), options).await
+}
@@ -0,0 +1,616 @@
+use std::fmt;
+use thiserror::Error;
+
+use crate::ipa::{tokenize, Phoneme, Stress};
+use crate::models::responses;
+
+/// ASCII phonetic notations IPA can be converted to and from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// CMU ARPAbet with stress digits, English only.
+    Arpabet,
+    XSampa,
+    /// Kirshenbaum (ASCII-IPA), as used by eSpeak.
+    Kirshenbaum,
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Notation::Arpabet => "ARPAbet",
+            Notation::XSampa => "X-SAMPA",
+            Notation::Kirshenbaum => "Kirshenbaum",
+        })
+    }
+}
+
+/// What to do with a symbol the target notation has no equivalent for.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Unmappable {
+    /// Copy the symbol into the output unchanged.
+    #[default]
+    Keep,
+    /// Leave the symbol out.
+    Drop,
+    /// Write the given placeholder instead.
+    Replace(String),
+    /// Fail with `ConversionError`.
+    Fail,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("no {notation} equivalent for {symbol:?}")]
+pub struct ConversionError {
+    pub symbol: String,
+    pub notation: Notation,
+}
+
+/// Converts between IPA and an ASCII notation.
+///
+/// # Examples
+///
+/// ```
+/// use rustruut::transcription::{Converter, Notation};
+/// let arpabet = Converter::new(Notation::Arpabet);
+/// assert_eq!(arpabet.from_ipa("həˈloʊ").unwrap(), "HH AH0 L OW1");
+/// assert_eq!(arpabet.to_ipa("HH AH0 L OW1").unwrap(), "həlˈoʊ");
+///
+/// let xsampa = Converter::new(Notation::XSampa);
+/// assert_eq!(xsampa.from_ipa("ˈʃɪp").unwrap(), "\"SIp");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Converter {
+    notation: Notation,
+    unmappable: Unmappable,
+}
+
+impl Converter {
+    pub fn new(notation: Notation) -> Self {
+        Self {
+            notation,
+            unmappable: Unmappable::default(),
+        }
+    }
+
+    pub fn with_unmappable(mut self, unmappable: Unmappable) -> Self {
+        self.unmappable = unmappable;
+        self
+    }
+
+    pub fn notation(&self) -> Notation {
+        self.notation
+    }
+
+    /// Convert IPA into the notation.
+    ///
+    /// ARPAbet symbols are space separated and words are separated by ` | `.
+    pub fn from_ipa(&self, ipa: &str) -> Result<String, ConversionError> {
+        match self.notation {
+            Notation::Arpabet => {
+                let words = ipa
+                    .split_whitespace()
+                    .map(|word| self.arpabet_word(word))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(words.join(" | "))
+            }
+            Notation::XSampa => self.substitute(ipa, XSAMPA, false),
+            Notation::Kirshenbaum => self.substitute(ipa, KIRSHENBAUM, false),
+        }
+    }
+
+    /// Convert text in the notation back into IPA, e.g. to feed an `is_reverse` request.
+    pub fn to_ipa(&self, text: &str) -> Result<String, ConversionError> {
+        match self.notation {
+            Notation::Arpabet => {
+                let mut words = vec![String::new()];
+                for token in text.split_whitespace() {
+                    if token == "|" {
+                        words.push(String::new());
+                        continue;
+                    }
+                    let ipa = self.arpabet_to_ipa(token)?;
+                    words.last_mut().unwrap().push_str(&ipa);
+                }
+                words.retain(|w| !w.is_empty());
+                Ok(words.join(" "))
+            }
+            Notation::XSampa => self.substitute(text, XSAMPA, true),
+            Notation::Kirshenbaum => self.substitute(text, KIRSHENBAUM, true),
+        }
+    }
+
+    /// Copy of `resp` with every `phonetic` converted into the notation.
+    pub fn convert_response(
+        &self,
+        resp: &responses::PhonemizeSentence,
+    ) -> Result<responses::PhonemizeSentence, ConversionError> {
+        let mut resp = resp.clone();
+        for word in &mut resp.words {
+            word.phonetic = self.from_ipa(&word.phonetic)?;
+        }
+        Ok(resp)
+    }
+
+    fn unmapped(&self, symbol: &str) -> Result<Option<String>, ConversionError> {
+        match &self.unmappable {
+            Unmappable::Keep => Ok(Some(symbol.to_string())),
+            Unmappable::Drop => Ok(None),
+            Unmappable::Replace(placeholder) => Ok(Some(placeholder.clone())),
+            Unmappable::Fail => Err(ConversionError {
+                symbol: symbol.to_string(),
+                notation: self.notation,
+            }),
+        }
+    }
+
+    /// Table driven conversion, longest match first, whitespace is kept.
+    fn substitute(
+        &self,
+        text: &str,
+        table: &[(&str, &str)],
+        reverse: bool,
+    ) -> Result<String, ConversionError> {
+        let mut rules: Vec<(&str, &str)> = table
+            .iter()
+            .map(|&(ipa, ascii)| if reverse { (ascii, ipa) } else { (ipa, ascii) })
+            .filter(|(from, _)| !from.is_empty())
+            .collect();
+        // stable sort keeps the table order for equally long alternatives
+        rules.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            if c.is_whitespace() {
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+            match rules.iter().find(|(from, _)| rest.starts_with(from)) {
+                Some((from, to)) => {
+                    out.push_str(to);
+                    rest = &rest[from.len()..];
+                }
+                None => {
+                    if let Some(symbol) = self.unmapped(&c.to_string())? {
+                        out.push_str(&symbol);
+                    }
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn arpabet_word(&self, ipa: &str) -> Result<String, ConversionError> {
+        let phonemes = tokenize(ipa);
+        let mut symbols: Vec<String> = Vec::new();
+        let mut stress = Stress::None;
+        let mut i = 0;
+
+        while i < phonemes.len() {
+            let phoneme = &phonemes[i];
+            if phoneme.stress != Stress::None {
+                stress = phoneme.stress;
+            }
+
+            let pair = phonemes
+                .get(i + 1)
+                .and_then(|next| arpabet_pair(phoneme, next));
+            let (symbol, consumed) = match pair {
+                Some(symbol) => (Some(symbol), 2),
+                None => (arpabet_single(&phoneme.base), 1),
+            };
+            i += consumed;
+
+            let Some(symbol) = symbol else {
+                if let Some(symbol) = self.unmapped(&phoneme.symbol())? {
+                    symbols.push(symbol);
+                }
+                continue;
+            };
+
+            // syllabic consonants get a schwa, e.g. bottle -> B AA1 T AH0 L
+            let syllabic = consumed == 1 && phoneme.diacritics.contains('\u{0329}');
+            if syllabic {
+                symbols.push(format!("AH{}", digit(stress)));
+                stress = Stress::None;
+            }
+            if ARPABET_VOWELS.contains(&symbol) {
+                symbols.push(format!("{}{}", symbol, digit(stress)));
+                stress = Stress::None;
+            } else {
+                symbols.push(symbol.to_string());
+            }
+        }
+        Ok(symbols.join(" "))
+    }
+
+    fn arpabet_to_ipa(&self, token: &str) -> Result<String, ConversionError> {
+        let upper = token.to_ascii_uppercase();
+        let (symbol, digit) = match upper.strip_suffix(['0', '1', '2']) {
+            Some(symbol) => (symbol, upper.chars().last()),
+            None => (upper.as_str(), None),
+        };
+        let ipa = match (symbol, digit) {
+            ("AH", Some('0')) => Some("ə"),
+            ("ER", Some('0')) => Some("ɚ"),
+            _ => ARPABET
+                .iter()
+                .find(|(_, arpabet)| *arpabet == symbol)
+                .map(|(ipa, _)| *ipa),
+        };
+        let Some(ipa) = ipa else {
+            return Ok(self.unmapped(token)?.unwrap_or_default());
+        };
+        let mark = match digit {
+            Some('1') => "ˈ",
+            Some('2') => "ˌ",
+            _ => "",
+        };
+        Ok(format!("{}{}", mark, ipa))
+    }
+}
+
+fn digit(stress: Stress) -> char {
+    match stress {
+        Stress::None => '0',
+        Stress::Primary => '1',
+        Stress::Secondary => '2',
+    }
+}
+
+fn arpabet_pair(first: &Phoneme, second: &Phoneme) -> Option<&'static str> {
+    if second.stress != Stress::None {
+        return None;
+    }
+    ARPABET_PAIRS
+        .iter()
+        .find(|((a, b), _)| *a == first.base && *b == second.base)
+        .map(|(_, arpabet)| *arpabet)
+}
+
+fn arpabet_single(base: &str) -> Option<&'static str> {
+    ARPABET
+        .iter()
+        .chain(ARPABET_EXTRA)
+        .find(|(ipa, _)| *ipa == base)
+        .map(|(_, arpabet)| *arpabet)
+}
+
+const ARPABET_VOWELS: &[&str] = &[
+    "AA", "AE", "AH", "AO", "AW", "AY", "EH", "ER", "EY", "IH", "IY", "OW", "OY", "UH", "UW",
+];
+
+/// Diphthongs and untied affricates, written as two IPA phonemes.
+const ARPABET_PAIRS: &[((&str, &str), &str)] = &[
+    (("a", "ɪ"), "AY"),
+    (("a", "ʊ"), "AW"),
+    (("e", "ɪ"), "EY"),
+    (("o", "ʊ"), "OW"),
+    (("ə", "ʊ"), "OW"),
+    (("ɔ", "ɪ"), "OY"),
+    (("t", "ʃ"), "CH"),
+    (("d", "ʒ"), "JH"),
+];
+
+/// IPA <-> ARPAbet, the IPA side is what `to_ipa` produces.
+const ARPABET: &[(&str, &str)] = &[
+    ("ɑ", "AA"),
+    ("æ", "AE"),
+    ("ʌ", "AH"),
+    ("ɔ", "AO"),
+    ("aʊ", "AW"),
+    ("aɪ", "AY"),
+    ("ɛ", "EH"),
+    ("ɝ", "ER"),
+    ("eɪ", "EY"),
+    ("ɪ", "IH"),
+    ("i", "IY"),
+    ("oʊ", "OW"),
+    ("ɔɪ", "OY"),
+    ("ʊ", "UH"),
+    ("u", "UW"),
+    ("b", "B"),
+    ("tʃ", "CH"),
+    ("d", "D"),
+    ("ð", "DH"),
+    ("f", "F"),
+    ("ɡ", "G"),
+    ("h", "HH"),
+    ("dʒ", "JH"),
+    ("k", "K"),
+    ("l", "L"),
+    ("m", "M"),
+    ("n", "N"),
+    ("ŋ", "NG"),
+    ("p", "P"),
+    ("ɹ", "R"),
+    ("s", "S"),
+    ("ʃ", "SH"),
+    ("t", "T"),
+    ("θ", "TH"),
+    ("v", "V"),
+    ("w", "W"),
+    ("j", "Y"),
+    ("z", "Z"),
+    ("ʒ", "ZH"),
+];
+
+/// Further IPA symbols of English dialects, only converted to ARPAbet.
+const ARPABET_EXTRA: &[(&str, &str)] = &[
+    ("ə", "AH"),
+    ("ɐ", "AH"),
+    ("ɚ", "ER"),
+    ("ɜ", "ER"),
+    ("a", "AA"),
+    ("ɒ", "AA"),
+    ("e", "EH"),
+    ("o", "OW"),
+    ("ɨ", "IH"),
+    ("ᵻ", "IH"),
+    ("ʉ", "UW"),
+    ("t͡ʃ", "CH"),
+    ("d͡ʒ", "JH"),
+    ("g", "G"),
+    ("r", "R"),
+    ("ɻ", "R"),
+    ("ɾ", "T"),
+    ("ɫ", "L"),
+    ("ʍ", "W"),
+];
+
+/// IPA <-> X-SAMPA. For reverse lookups the first of equally long entries wins.
+const XSAMPA: &[(&str, &str)] = &[
+    // vowels
+    ("i", "i"),
+    ("y", "y"),
+    ("ɨ", "1"),
+    ("ʉ", "}"),
+    ("ɯ", "M"),
+    ("u", "u"),
+    ("ɪ", "I"),
+    ("ʏ", "Y"),
+    ("ʊ", "U"),
+    ("e", "e"),
+    ("ø", "2"),
+    ("ɘ", "@\\"),
+    ("ɵ", "8"),
+    ("ɤ", "7"),
+    ("o", "o"),
+    ("ə", "@"),
+    ("ɛ", "E"),
+    ("œ", "9"),
+    ("ɜ", "3"),
+    ("ɞ", "3\\"),
+    ("ʌ", "V"),
+    ("ɔ", "O"),
+    ("æ", "{"),
+    ("ɐ", "6"),
+    ("a", "a"),
+    ("ɶ", "&"),
+    ("ɑ", "A"),
+    ("ɒ", "Q"),
+    ("ɚ", "@`"),
+    ("ɝ", "3`"),
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("ʈ", "t`"),
+    ("ɖ", "d`"),
+    ("c", "c"),
+    ("ɟ", "J\\"),
+    ("k", "k"),
+    ("ɡ", "g"),
+    ("g", "g"),
+    ("q", "q"),
+    ("ɢ", "G\\"),
+    ("ʔ", "?"),
+    ("m", "m"),
+    ("ɱ", "F"),
+    ("n", "n"),
+    ("ɳ", "n`"),
+    ("ɲ", "J"),
+    ("ŋ", "N"),
+    ("ɴ", "N\\"),
+    ("ʙ", "B\\"),
+    ("r", "r"),
+    ("ʀ", "R\\"),
+    ("ɾ", "4"),
+    ("ɽ", "r`"),
+    ("ɸ", "p\\"),
+    ("β", "B"),
+    ("f", "f"),
+    ("v", "v"),
+    ("θ", "T"),
+    ("ð", "D"),
+    ("s", "s"),
+    ("z", "z"),
+    ("ʃ", "S"),
+    ("ʒ", "Z"),
+    ("ʂ", "s`"),
+    ("ʐ", "z`"),
+    ("ç", "C"),
+    ("ʝ", "j\\"),
+    ("x", "x"),
+    ("ɣ", "G"),
+    ("χ", "X"),
+    ("ʁ", "R"),
+    ("ħ", "X\\"),
+    ("ʕ", "?\\"),
+    ("h", "h"),
+    ("ɦ", "h\\"),
+    ("ɬ", "K"),
+    ("ɮ", "K\\"),
+    ("ʋ", "P"),
+    ("ɹ", "r\\"),
+    ("ɻ", "r\\`"),
+    ("j", "j"),
+    ("ɰ", "M\\"),
+    ("l", "l"),
+    ("ɭ", "l`"),
+    ("ʎ", "L"),
+    ("ʟ", "L\\"),
+    ("ɫ", "5"),
+    ("ɺ", "l\\"),
+    ("w", "w"),
+    ("ʍ", "W"),
+    ("ɥ", "H"),
+    ("ɕ", "s\\"),
+    ("ʑ", "z\\"),
+    ("ɧ", "x\\"),
+    ("ʜ", "H\\"),
+    ("ʢ", "<\\"),
+    ("ʡ", ">\\"),
+    ("ɓ", "b_<"),
+    ("ɗ", "d_<"),
+    ("ɠ", "g_<"),
+    // suprasegmentals
+    ("ˈ", "\""),
+    ("ˌ", "%"),
+    ("ː", ":"),
+    ("ˑ", ":\\"),
+    (".", "."),
+    ("‿", "-\\"),
+    ("|", "|"),
+    ("‖", "||"),
+    // diacritics
+    ("\u{0361}", "_"),
+    ("ʰ", "_h"),
+    ("ʷ", "_w"),
+    ("ʲ", "'"),
+    ("ˠ", "_G"),
+    ("ˤ", "_?\\"),
+    ("ⁿ", "_n"),
+    ("ˡ", "_l"),
+    ("ʼ", "_>"),
+    ("˞", "`"),
+    ("\u{0303}", "~"),
+    ("\u{0325}", "_0"),
+    ("\u{030A}", "_0"),
+    ("\u{0329}", "="),
+    ("\u{032A}", "_d"),
+    ("\u{032F}", "_^"),
+    ("\u{031A}", "_}"),
+    ("\u{032C}", "_v"),
+    ("\u{0324}", "_t"),
+    ("\u{0330}", "_k"),
+    ("\u{031D}", "_r"),
+    ("\u{031E}", "_o"),
+    ("\u{0318}", "_A"),
+    ("\u{0319}", "_q"),
+    ("\u{0308}", "_\""),
+    ("\u{033D}", "_x"),
+    ("\u{0306}", "_X"),
+];
+
+/// IPA <-> Kirshenbaum. For reverse lookups the first of equally long entries wins.
+const KIRSHENBAUM: &[(&str, &str)] = &[
+    // vowels
+    ("i", "i"),
+    ("y", "y"),
+    ("ɨ", "i\""),
+    ("ʉ", "u\""),
+    ("ɯ", "u-"),
+    ("u", "u"),
+    ("ɪ", "I"),
+    ("ʏ", "I."),
+    ("ʊ", "U"),
+    ("e", "e"),
+    ("ø", "Y"),
+    ("ɵ", "@."),
+    ("ɤ", "o-"),
+    ("o", "o"),
+    ("ə", "@"),
+    ("ɛ", "E"),
+    ("œ", "W"),
+    ("ɜ", "V\""),
+    ("ʌ", "V"),
+    ("ɔ", "O"),
+    ("æ", "&"),
+    ("ɐ", "a#"),
+    ("a", "a"),
+    ("ɶ", "&."),
+    ("ɑ", "A"),
+    ("ɒ", "A."),
+    ("ɚ", "R"),
+    ("ɝ", "R<umd>"),
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("ʈ", "t."),
+    ("ɖ", "d."),
+    ("c", "c"),
+    ("ɟ", "J"),
+    ("k", "k"),
+    ("ɡ", "g"),
+    ("g", "g"),
+    ("q", "q"),
+    ("ɢ", "G"),
+    ("ʔ", "?"),
+    ("m", "m"),
+    ("ɱ", "M"),
+    ("n", "n"),
+    ("ɳ", "n."),
+    ("ɲ", "n^"),
+    ("ŋ", "N"),
+    ("ɴ", "n\""),
+    ("ʙ", "b<trl>"),
+    ("r", "r<trl>"),
+    ("ʀ", "r\""),
+    ("ɾ", "*"),
+    ("ɽ", "*."),
+    ("ɸ", "P"),
+    ("β", "B"),
+    ("f", "f"),
+    ("v", "v"),
+    ("θ", "T"),
+    ("ð", "D"),
+    ("s", "s"),
+    ("z", "z"),
+    ("ʃ", "S"),
+    ("ʒ", "Z"),
+    ("ʂ", "s."),
+    ("ʐ", "z."),
+    ("ç", "C"),
+    ("ʝ", "C<vcd>"),
+    ("x", "x"),
+    ("ɣ", "Q"),
+    ("χ", "X"),
+    ("ʁ", "g\""),
+    ("ħ", "H"),
+    ("ʕ", "H<vcd>"),
+    ("h", "h"),
+    ("ɦ", "h<?>"),
+    ("ɬ", "s<lat>"),
+    ("ɮ", "z<lat>"),
+    ("ʋ", "r<lbd>"),
+    ("ɹ", "r"),
+    ("ɻ", "r."),
+    ("j", "j"),
+    ("ɰ", "j<vel>"),
+    ("l", "l"),
+    ("ɭ", "l."),
+    ("ʎ", "l^"),
+    ("ʟ", "L"),
+    ("ɫ", "l<vel>"),
+    ("w", "w"),
+    ("ʍ", "w<vls>"),
+    ("ɥ", "w<pal>"),
+    // suprasegmentals and diacritics
+    ("ˈ", "'"),
+    ("ˌ", ","),
+    ("ː", ":"),
+    ("ʰ", "<h>"),
+    ("ʷ", "<w>"),
+    ("ʲ", ";"),
+    ("\u{0303}", "~"),
+    ("\u{0329}", "-"),
+    ("\u{0325}", "<o>"),
+    ("\u{032A}", "["),
+    ("\u{0361}", ""),
+];
@@ -0,0 +1,203 @@
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::models::{requests, responses};
+use crate::options::DiskCacheOptions;
+
+/// Name of the cache folder inside the download directory.
+const CACHE_DIR: &str = "rustruut-cache";
+
+/// Folders of other engine versions unused for this long are removed on `open`.
+const STALE_AFTER: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// File-backed phonemization cache, one JSON file per request.
+///
+/// Entries live in `<download dir>/rustruut-cache/<engine version>/<sha256>.json`,
+/// so results of a different goruut release are never served. Folders of other
+/// versions may still be in use by other processes, they are only removed once
+/// unused for 30 days. Least recently used entries are evicted when the size
+/// limits are exceeded.
+pub struct DiskCache {
+    dir: PathBuf,
+    options: DiskCacheOptions,
+    bytes: AtomicU64,
+    entries: AtomicU64,
+    // serializes eviction sweeps within the process
+    evicting: Mutex<()>,
+}
+
+impl DiskCache {
+    pub fn open(
+        download_dir: &Path,
+        engine_version: &str,
+        options: DiskCacheOptions,
+    ) -> io::Result<Self> {
+        let root = download_dir.join(CACHE_DIR);
+        let version_dir = sanitize(engine_version);
+        fs::create_dir_all(root.join(&version_dir))?;
+
+        // Drop what other releases cached unless it is still in use
+        let now = SystemTime::now();
+        for entry in fs::read_dir(&root)?.flatten() {
+            if entry.file_name() == version_dir.as_str() {
+                continue;
+            }
+            let stale = last_use(&entry.path())
+                .is_some_and(|t| now.duration_since(t).unwrap_or_default() > STALE_AFTER);
+            if stale {
+                let _ = fs::remove_dir_all(entry.path());
+            }
+        }
+
+        let cache = Self {
+            dir: root.join(version_dir),
+            options,
+            bytes: AtomicU64::new(0),
+            entries: AtomicU64::new(0),
+            evicting: Mutex::new(()),
+        };
+        let files = cache.files()?;
+        cache.entries.store(files.len() as u64, Ordering::SeqCst);
+        cache
+            .bytes
+            .store(files.iter().map(|f| f.1).sum(), Ordering::SeqCst);
+        Ok(cache)
+    }
+
+    /// Folder holding the entries of the current engine version.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Total size of the cached responses in bytes.
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::SeqCst)
+    }
+
+    /// Number of cached responses.
+    pub fn entries(&self) -> u64 {
+        self.entries.load(Ordering::SeqCst)
+    }
+
+    fn path(&self, req: &requests::PhonemizeSentence) -> PathBuf {
+        let key = serde_json::to_vec(req).unwrap_or_default();
+        let hash = Sha256::digest(&key);
+        self.dir.join(format!("{:x}.json", hash))
+    }
+
+    pub fn get(&self, req: &requests::PhonemizeSentence) -> Option<responses::PhonemizeSentence> {
+        let path = self.path(req);
+        let data = fs::read(&path).ok()?;
+        // Touch the entry so eviction sees it as recently used
+        if let Ok(file) = File::options().write(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        serde_json::from_slice(&data).ok()
+    }
+
+    pub fn put(
+        &self,
+        req: &requests::PhonemizeSentence,
+        resp: &responses::PhonemizeSentence,
+    ) -> io::Result<()> {
+        let path = self.path(req);
+        let data = serde_json::to_vec(resp)?;
+
+        // Write to a temp file and rename, readers never see a partial entry
+        let tmp = tempfile::NamedTempFile::new_in(&self.dir)?;
+        fs::write(tmp.path(), &data)?;
+        let replaced = fs::metadata(&path).ok().map(|m| m.len());
+        tmp.persist(&path).map_err(|e| e.error)?;
+
+        // A replaced entry only changes the size by the difference
+        self.bytes.fetch_add(data.len() as u64, Ordering::SeqCst);
+        match replaced {
+            Some(len) => {
+                self.bytes.fetch_sub(len, Ordering::SeqCst);
+            }
+            None => {
+                self.entries.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        if self.bytes.load(Ordering::SeqCst) > self.options.max_bytes
+            || self.entries.load(Ordering::SeqCst) > self.options.max_entries as u64
+        {
+            self.evict()?;
+        }
+        Ok(())
+    }
+
+    /// Remove least recently used entries until the cache is below 90% of its limits.
+    pub fn evict(&self) -> io::Result<()> {
+        let _guard = self.evicting.lock().unwrap();
+
+        let mut files = self.files()?;
+        files.sort_by_key(|f| f.2);
+
+        let target_bytes = self.options.max_bytes - self.options.max_bytes / 10;
+        let target_entries = self.options.max_entries - self.options.max_entries / 10;
+        let mut bytes: u64 = files.iter().map(|f| f.1).sum();
+        let mut entries = files.len();
+
+        for (path, len, _) in files {
+            if bytes <= target_bytes && entries <= target_entries {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                bytes -= len;
+                entries -= 1;
+            }
+        }
+
+        self.bytes.store(bytes, Ordering::SeqCst);
+        self.entries.store(entries as u64, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// All entries as (path, size, last use).
+    fn files(&self) -> io::Result<Vec<(PathBuf, u64, SystemTime)>> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&self.dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|e| e != "json") {
+                continue;
+            }
+            if let Ok(meta) = entry.metadata() {
+                let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                files.push((path, meta.len(), modified));
+            }
+        }
+        Ok(files)
+    }
+}
+
+/// Latest modification of `dir` or one of its entries, entries are touched on use.
+fn last_use(dir: &Path) -> Option<SystemTime> {
+    let mut last = fs::metadata(dir).and_then(|m| m.modified()).ok()?;
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+            last = last.max(modified);
+        }
+    }
+    Some(last)
+}
+
+/// Make an engine version usable as a folder name.
+fn sanitize(version: &str) -> String {
+    version
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,136 @@
+use std::ops::Range;
+
+use crate::models::responses;
+
+/// Punctuation ending a sentence, preferred as a chunk boundary.
+const SENTENCE_END: &[char] = &['.', '!', '?', '…', '。', '！', '？', '؟', '।'];
+/// Punctuation ending a clause, used when no sentence boundary fits.
+const CLAUSE_END: &[char] = &[',', ';', ':', '،', '、', '，', '；', '：', '—', ')'];
+
+/// A piece of a long input sentence which fits below the word limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// The chunk as written in the original text, spacing included.
+    pub text: String,
+    /// Byte range of `text` in the original text.
+    pub range: Range<usize>,
+    /// Whether the chunk begins a new sentence of the original text.
+    pub starts_sentence: bool,
+}
+
+/// Number of words the way the policy limit counts them.
+pub fn word_count(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+/// Byte range of every word, split like `word_count`.
+fn word_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                ranges.push(s..i);
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        ranges.push(s..text.len());
+    }
+    ranges
+}
+
+fn boundary_rank(word: &str) -> u8 {
+    let word = word.trim_end_matches(['"', '\'', '»', '”', '’']);
+    match word.chars().last() {
+        Some(c) if SENTENCE_END.contains(&c) => 2,
+        Some(c) if CLAUSE_END.contains(&c) => 1,
+        _ => 0,
+    }
+}
+
+/// Split `text` into chunks of at most `max_words` words.
+///
+/// A chunk is cut after the last sentence end that fits, else after the last
+/// clause end, else right at the limit. Chunks are sliced from `text`, so the
+/// spacing between their words is kept.
+pub fn split(text: &str, max_words: usize) -> Vec<Chunk> {
+    let max_words = max_words.max(1);
+    let ranges = word_ranges(text);
+    let words: Vec<&str> = ranges.iter().map(|r| &text[r.clone()]).collect();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut starts_sentence = true;
+
+    while start < words.len() {
+        let mut end = (start + max_words).min(words.len());
+        let mut rank = 2;
+        if end < words.len() {
+            // best boundary inside the window: highest rank, then latest position
+            let (best_end, best_rank) = (start + 1..=end)
+                .map(|e| (e, boundary_rank(words[e - 1])))
+                .max_by_key(|&(e, r)| (r, e))
+                .unwrap();
+            if best_rank > 0 {
+                end = best_end;
+            }
+            rank = best_rank;
+        }
+
+        let range = ranges[start].start..ranges[end - 1].end;
+        chunks.push(Chunk {
+            text: text[range.clone()].to_string(),
+            range,
+            starts_sentence,
+        });
+        starts_sentence = rank == 2;
+        start = end;
+    }
+    chunks
+}
+
+/// Stitch the responses of `chunks` back into one response.
+///
+/// Word flags are fixed up so they match a single request over the whole text:
+/// with `split_sentences` every sentence keeps its own first/last word, otherwise
+/// only the first and last word of the text are flagged.
+pub fn stitch(
+    chunks: &[Chunk],
+    responses: Vec<responses::PhonemizeSentence>,
+    split_sentences: bool,
+) -> responses::PhonemizeSentence {
+    let mut out = responses::PhonemizeSentence::default();
+    for (chunk, resp) in chunks.iter().zip(responses) {
+        let boundary = split_sentences && chunk.starts_sentence;
+        let mut words = resp.words;
+        if !out.words.is_empty() && !boundary {
+            if let Some(last) = out.words.last_mut() {
+                last.is_last = false;
+            }
+            if let Some(first) = words.first_mut() {
+                first.is_first = false;
+            }
+        }
+        out.error_word_limit_exceeded |= resp.error_word_limit_exceeded;
+        out.words.extend(words);
+    }
+    out
+}
+
+/// Set `is_first`/`is_last` for words assembled outside of goruut.
+///
+/// The whole text is one sentence unless `split_sentences` is set, then a word
+/// whose trailing punctuation ends a sentence closes it.
+pub fn sentence_flags(words: &mut [responses::PhonemizeSentenceWord], split_sentences: bool) {
+    let n = words.len();
+    let mut starts = true;
+    for (i, word) in words.iter_mut().enumerate() {
+        let ends = i + 1 == n || (split_sentences && boundary_rank(&word.post_punct) == 2);
+        word.is_first = starts;
+        word.is_last = ends;
+        starts = ends;
+    }
+}
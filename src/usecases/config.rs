@@ -5,6 +5,7 @@ use serde::Serialize;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
+use std::marker::PhantomData;
 
 #[derive(Serialize)]
 struct LoadModel {
@@ -31,12 +32,10 @@ where
     V: Version,
 {
     policy: P,
-    ipa: I,
-    dict: D,
     api: A,
-    folder: F,
-    version: V,
     port: u16,
+    // the remaining providers are not part of the config file
+    providers: PhantomData<(I, D, F, V)>,
 }
 
 impl<P, I, D, A, F, V> Config<P, I, D, A, F, V>
@@ -52,12 +51,9 @@ where
         let port = rand::thread_rng().gen_range(1024..=65535);
         Self {
             policy: di.policy.clone(),
-            ipa: di.ipa.clone(),
-            dict: di.dict_getter.clone(),
             api: di.api.clone(),
-            version: di.version.clone(),
-            port: port,
-            folder: di.folder.clone(),
+            port,
+            providers: PhantomData,
         }
     }
 
@@ -66,6 +62,14 @@ where
         filename: &str,
         models: &HashMap<String, String>,
     ) -> std::io::Result<()> {
+        let mut file = File::create(filename)?;
+        let json = self.to_json(models)?;
+        file.write_all(json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Content of the config file `serialize` writes.
+    pub fn to_json(&self, models: &HashMap<String, String>) -> serde_json::Result<String> {
         let mut load_models = Vec::new();
         for (lang, file) in models {
             load_models.push(LoadModel {
@@ -73,6 +77,7 @@ where
                 file: file.clone(),
             });
         }
+        load_models.sort_by(|a, b| a.lang.cmp(&b.lang));
 
         let data = ConfigData {
             port: self.port.to_string(),
@@ -85,10 +90,7 @@ where
             },
         };
 
-        let mut file = File::create(filename)?;
-        let json = serde_json::to_string_pretty(&data)?;
-        file.write_all(json.as_bytes())?;
-        Ok(())
+        serde_json::to_string_pretty(&data)
     }
 
     pub fn url(&self, subpath: &str) -> String {
@@ -103,6 +105,6 @@ where
     }
 
     pub fn get_port(&self) -> u16 {
-        return self.port;
+        self.port
     }
 }
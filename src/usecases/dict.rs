@@ -0,0 +1,97 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::interfaces::DictGetter;
+use crate::lexicon::{Lexicon, LexiconError};
+
+use super::languages::Languages;
+
+/// Name of the folder inside the writeable dir holding materialized models.
+const MODELS_DIR: &str = "rustruut-models";
+
+/// Path the `DictGetter` is asked for to get the model of `language`,
+/// e.g. `English.zlib` or `EnglishBritish.zlib`.
+pub fn model_path(language: &str) -> String {
+    format!("{}.zlib", language)
+}
+
+/// Path the `DictGetter` is asked for to get the dictionary of `language`,
+/// e.g. `English.tsv`, in the format `Lexicon::parse_tsv` reads.
+pub fn dictionary_path(language: &str) -> String {
+    format!("{}.tsv", language)
+}
+
+/// Ask `dict` for the dictionary of every known language and add them to
+/// `lexicon`, entries already in it take precedence.
+///
+/// goruut's config has no place for dictionaries, so they are applied on the
+/// client side like `PhonemizerOptions::lexicon`.
+pub fn add_dictionaries<D: DictGetter>(
+    dict: &D,
+    lexicon: &mut Option<Lexicon>,
+) -> Result<(), LexiconError> {
+    let mut dictionaries = Lexicon::new();
+    for language in Languages::new().languages() {
+        let Some(data) = dict.get(&dictionary_path(language.name())) else {
+            continue;
+        };
+        let text =
+            String::from_utf8(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        dictionaries.parse_tsv(language.name(), &text)?;
+    }
+    if dictionaries.is_empty() {
+        return Ok(());
+    }
+    if let Some(user) = lexicon.take() {
+        dictionaries.extend(&user);
+    }
+    *lexicon = Some(dictionaries);
+    Ok(())
+}
+
+/// Ask `dict` for the model of every known language and write the ones it has
+/// into `<dir>/rustruut-models/`.
+///
+/// Returns language -> model file, ready for the goruut config `LoadModels`.
+pub fn materialize<D: DictGetter>(dict: &D, dir: &Path) -> io::Result<HashMap<String, String>> {
+    store(dir, fetch(dict))
+}
+
+/// Models `dict` provides, as (language, file content).
+pub fn fetch<D: DictGetter>(dict: &D) -> Vec<(String, Vec<u8>)> {
+    Languages::new()
+        .languages()
+        .into_iter()
+        .filter_map(|language| {
+            let data = dict.get(&model_path(language.name()))?;
+            Some((language.name().to_string(), data))
+        })
+        .collect()
+}
+
+/// Write fetched models into `<dir>/rustruut-models/`. Files are named by
+/// content hash, so unchanged models are not rewritten.
+pub fn store(dir: &Path, models: Vec<(String, Vec<u8>)>) -> io::Result<HashMap<String, String>> {
+    let mut files = HashMap::new();
+    if models.is_empty() {
+        return Ok(files);
+    }
+
+    let models_dir = dir.join(MODELS_DIR);
+    fs::create_dir_all(&models_dir)?;
+    for (language, data) in models {
+        let hash = Sha256::digest(&data);
+        let path = models_dir.join(format!("{}-{:x}.zlib", language, hash));
+        if !path.exists() {
+            // Write to a temp file and rename, goruut never loads a partial model
+            let tmp = tempfile::NamedTempFile::new_in(&models_dir)?;
+            fs::write(tmp.path(), &data)?;
+            tmp.persist(&path).map_err(|e| e.error)?;
+        }
+        files.insert(language, path.to_string_lossy().into_owned());
+    }
+    Ok(files)
+}
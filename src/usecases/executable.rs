@@ -1,16 +1,50 @@
 use indicatif::style::TemplateError;
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, CONTENT_RANGE, RANGE};
+use reqwest::StatusCode;
 use sha2::{Digest, Sha256};
-use std::fs::{self, File};
-use std::io::{self, copy, BufReader, Read, Write};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use thiserror::Error;
 
 use super::platform::{Architecture, OS};
 
-#[derive(Debug)]
+/// Longest wait for the next bytes of a download before the attempt is given up.
+const STALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Attempts in a row receiving nothing before moving on to the next server.
+const MAX_IDLE_ATTEMPTS: usize = 3;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Length and running sha256 of the bytes downloaded so far.
+struct Received {
+    len: u64,
+    hasher: Sha256,
+}
+
+impl Received {
+    fn new() -> Self {
+        Self {
+            len: 0,
+            hasher: Sha256::new(),
+        }
+    }
+
+    fn update(&mut self, chunk: &[u8]) {
+        self.len += chunk.len() as u64;
+        self.hasher.update(chunk);
+    }
+
+    fn sha256(&self) -> String {
+        format!("{:x}", self.hasher.clone().finalize())
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Executable {
     pub size: u64,
     pub sha256: String,
@@ -44,14 +78,14 @@ impl Executable {
             "goruut.{}.{}.{}{}",
             self.sha256,
             arch,
-            self.os.to_string(),
+            self.os,
             os_ext
         )
     }
 
     pub fn file_name_public(&self) -> String {
         let arch = self.architecture.to_string();
-        format!("goruut-{}-{}", self.os.to_string(), arch)
+        format!("goruut-{}-{}", self.os, arch)
     }
 
     pub fn exists(&self, temp_dir: &Path) -> Result<PathBuf, ExecutableError> {
@@ -92,78 +126,391 @@ impl Executable {
         Ok(temp_file_path)
     }
 
+    fn part_path(&self, temp_dir: &Path) -> PathBuf {
+        temp_dir.join(format!("{}.part", self.file_name()))
+    }
+
+    /// Open the part file for appending and hash what an earlier download left in it.
+    fn open_part(&self, path: &Path) -> io::Result<(File, Received)> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        if file.metadata()?.len() > self.size {
+            file.set_len(0)?;
+        }
+
+        let mut received = Received::new();
+        let mut buffer = vec![0; CHUNK_SIZE];
+        loop {
+            let bytes_read = file.read(&mut buffer)?;
+            if bytes_read == 0 {
+                break;
+            }
+            received.update(&buffer[..bytes_read]);
+        }
+        Ok((file, received))
+    }
+
+    /// Whether a response continues the part file at `offset` rather than starting over.
+    fn resumes(
+        status: StatusCode,
+        headers: &HeaderMap,
+        offset: u64,
+    ) -> Result<bool, ExecutableError> {
+        if status == StatusCode::PARTIAL_CONTENT {
+            let expected = format!("bytes {}-", offset);
+            let range = headers.get(CONTENT_RANGE).and_then(|v| v.to_str().ok());
+            return match range {
+                Some(range) if range.starts_with(&expected) => Ok(true),
+                _ => Err(ExecutableError::Download(format!(
+                    "Unexpected content range: {:?}",
+                    range
+                ))),
+            };
+        }
+        if !status.is_success() {
+            return Err(ExecutableError::Download(format!("HTTP error: {}", status)));
+        }
+        Ok(false)
+    }
+
+    /// The part of `chunk` still missing from the executable.
+    fn missing<'a>(&self, received: &Received, chunk: &'a [u8]) -> &'a [u8] {
+        let missing = usize::try_from(self.size - received.len).unwrap_or(usize::MAX);
+        &chunk[..chunk.len().min(missing)]
+    }
+
+    fn check_complete(&self, received: &Received) -> Result<(), ExecutableError> {
+        if received.len < self.size {
+            return Err(ExecutableError::Download(format!(
+                "Incomplete download, {} of {} bytes",
+                received.len, self.size
+            )));
+        }
+        Ok(())
+    }
+
+    fn progress_bar(&self) -> Result<ProgressBar, ExecutableError> {
+        let pb = ProgressBar::new(self.size);
+        pb.set_style(
+            ProgressStyle::with_template(
+                "[{elapsed_precise}] {bar:40.cyan/blue} {bytes}/{total_bytes} ({eta})",
+            )?
+            .progress_chars("##-"),
+        );
+        Ok(pb)
+    }
+
+    fn make_executable(path: &Path) -> Result<(), ExecutableError> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = fs::metadata(path)?.permissions();
+            perms.set_mode(0o755);
+            fs::set_permissions(path, perms)?;
+        }
+        #[cfg(not(unix))]
+        let _ = path;
+        Ok(())
+    }
+
+    /// Local path of a `file://` URL.
+    fn local_path(url: &str) -> Option<PathBuf> {
+        let url = reqwest::Url::parse(url).ok()?;
+        if url.scheme() != "file" {
+            return None;
+        }
+        url.to_file_path().ok()
+    }
+
+    /// Download into `<file name>.part` and move it into place once its sha256 matches.
+    ///
+    /// Dropped connections resume with an HTTP range request, so do later calls
+    /// when a part file is left over. A server is given up after it sent nothing
+    /// for `STALL_TIMEOUT` several times in a row.
     pub fn download(&self, temp_dir: &Path) -> Result<PathBuf, ExecutableError> {
-        let temp_file_path = temp_dir.join(self.file_name());
+        let part_path = self.part_path(temp_dir);
+        let (mut part, mut received) = self.open_part(&part_path)?;
+        let client = Client::builder()
+            .connect_timeout(STALL_TIMEOUT)
+            // the blocking client applies it to every read of the body
+            .timeout(STALL_TIMEOUT)
+            .build()
+            .map_err(|e| ExecutableError::Download(e.to_string()))?;
+        let pb = self.progress_bar()?;
+        pb.set_position(received.len);
         let mut last_error = None;
 
         for url_prefix in &self.servers {
             let url = format!("{}{}", url_prefix, self.file_name_public());
 
-            const BYTES_PER_SEC: u64 = 100_000;
+            let mut idle_attempts = 0;
+            while received.len < self.size && idle_attempts < MAX_IDLE_ATTEMPTS {
+                let before = received.len;
+                match self.fetch(&client, &url, &mut part, &mut received, &pb) {
+                    Ok(()) => {}
+                    Err(ExecutableError::Download(e)) => last_error = Some(e),
+                    Err(e) => return Err(e),
+                }
+                idle_attempts = if received.len == before {
+                    idle_attempts + 1
+                } else {
+                    0
+                };
+            }
+            if received.len < self.size {
+                continue;
+            }
+
+            if received.sha256() == self.sha256 {
+                pb.finish_with_message("Download complete");
+                drop(part);
+                let path = temp_dir.join(self.file_name());
+                fs::rename(&part_path, &path)?;
+                Self::make_executable(&path)?;
+                return Ok(path);
+            }
+            last_error = Some("SHA256 mismatch".to_string());
+            part.set_len(0)?;
+            received = Received::new();
+            pb.set_position(0);
+        }
 
-            // ceil(file_size / BYTES_PER_SEC)
-            let seconds = (self.size + BYTES_PER_SEC - 1) / BYTES_PER_SEC;
-            let timeout = Duration::from_secs(seconds.max(1) as u64);
+        pb.abandon();
+        Err(ExecutableError::Download(last_error.unwrap_or_else(|| {
+            "All download attempts failed".to_string()
+        })))
+    }
 
-            let client = match Client::builder().timeout(timeout).build() {
-                Ok(client) => client,
-                Err(e) => {
-                    last_error = Some(e.to_string());
-                    continue;
+    /// One attempt at receiving the rest of the executable from `url`.
+    fn fetch(
+        &self,
+        client: &Client,
+        url: &str,
+        part: &mut File,
+        received: &mut Received,
+        pb: &ProgressBar,
+    ) -> Result<(), ExecutableError> {
+        let mut source: Box<dyn Read> = match Self::local_path(url) {
+            Some(path) => {
+                let local_error =
+                    |e: io::Error| ExecutableError::Download(format!("{}: {}", path.display(), e));
+                let mut file = File::open(&path).map_err(local_error)?;
+                file.seek(SeekFrom::Start(received.len))
+                    .map_err(local_error)?;
+                Box::new(file)
+            }
+            None => {
+                let mut request = client.get(url);
+                if received.len > 0 {
+                    request = request.header(RANGE, format!("bytes={}-", received.len));
                 }
-            };
-
-            let response = match client.get(&url).send() {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = Some(e.to_string());
-                    continue;
+                let response = request
+                    .send()
+                    .map_err(|e| ExecutableError::Download(e.to_string()))?;
+                if !Self::resumes(response.status(), response.headers(), received.len)? {
+                    part.set_len(0)?;
+                    *received = Received::new();
+                    pb.set_position(0);
                 }
-            };
+                Box::new(response)
+            }
+        };
 
-            if !response.status().is_success() {
-                last_error = Some(format!("HTTP error: {}", response.status()));
-                continue;
+        let mut buffer = vec![0; CHUNK_SIZE];
+        while received.len < self.size {
+            let bytes_read = source
+                .read(&mut buffer)
+                .map_err(|e| ExecutableError::Download(e.to_string()))?;
+            if bytes_read == 0 {
+                break;
             }
+            let chunk = self.missing(received, &buffer[..bytes_read]);
+            part.write_all(chunk)?;
+            received.update(chunk);
+            pb.inc(chunk.len() as u64);
+        }
+        self.check_complete(received)
+    }
 
-            let pb = ProgressBar::new(self.size);
-            pb.set_style(
-                ProgressStyle::with_template(
-                    "[{elapsed_precise}] {bar:40.cyan/blue} {bytes}/{total_bytes} ({eta})",
-                )?
-                .progress_chars("##-"),
-            );
-
-            let mut source = response;
-            let mut reader = pb.wrap_read(BufReader::new(&mut source));
-            let mut file = File::create(&temp_file_path)?;
-
-            copy(&mut reader, &mut file)?;
-
-            pb.finish_with_message("Download complete");
-
-            // Verify the downloaded file
-            match self.exists(temp_dir) {
-                Ok(path) => {
-                    // Make executable
-                    #[cfg(unix)]
-                    {
-                        use std::os::unix::fs::PermissionsExt;
-                        let mut perms = fs::metadata(&path)?.permissions();
-                        perms.set_mode(0o755);
-                        fs::set_permissions(&path, perms)?;
-                    }
-                    return Ok(path);
-                }
-                Err(e) => {
-                    last_error = Some(e.to_string());
-                    fs::remove_file(&temp_file_path).ok(); // Ignore errors removing temp file
+    /// Exclusive lock on `<file name>.lock` in `temp_dir`, held until the file is dropped.
+    fn lock(&self, temp_dir: &Path) -> Result<File, ExecutableError> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(temp_dir.join(format!("{}.lock", self.file_name())))?;
+        file.lock()?;
+        Ok(file)
+    }
+
+    /// The verified executable in `temp_dir`, downloaded first when it is missing.
+    ///
+    /// Processes sharing `temp_dir` take turns through a lock file, so one of
+    /// them downloads and the others find the executable in place. It only
+    /// appears under its final name once complete, moved there by a rename.
+    pub fn exists_or_download(&self, temp_dir: &Path) -> Result<PathBuf, ExecutableError> {
+        let _lock = self.lock(temp_dir)?;
+        match self.exists(temp_dir) {
+            Ok(path) => Ok(path),
+            Err(_) => self.download(temp_dir),
+        }
+    }
+
+    /// Non-blocking variant of `exists_or_download`, waiting for the lock on the blocking thread pool.
+    #[cfg(feature = "async")]
+    pub async fn exists_or_download_async(
+        &self,
+        temp_dir: &Path,
+    ) -> Result<PathBuf, ExecutableError> {
+        let (executable, dir) = (self.clone(), temp_dir.to_path_buf());
+        let _lock = tokio::task::spawn_blocking(move || executable.lock(&dir))
+            .await
+            .map_err(|e| ExecutableError::Download(e.to_string()))??;
+        match self.exists_async(temp_dir).await {
+            Ok(path) => Ok(path),
+            Err(_) => self.download_async(temp_dir).await,
+        }
+    }
+
+    /// Non-blocking variant of `exists`, hashing runs on the blocking thread pool.
+    #[cfg(feature = "async")]
+    pub async fn exists_async(&self, temp_dir: &Path) -> Result<PathBuf, ExecutableError> {
+        let executable = self.clone();
+        let temp_dir = temp_dir.to_path_buf();
+        tokio::task::spawn_blocking(move || executable.exists(&temp_dir))
+            .await
+            .map_err(|e| ExecutableError::Verification(e.to_string()))?
+    }
+
+    /// Non-blocking variant of `download`.
+    #[cfg(feature = "async")]
+    pub async fn download_async(&self, temp_dir: &Path) -> Result<PathBuf, ExecutableError> {
+        let part_path = self.part_path(temp_dir);
+        let (executable, path) = (self.clone(), part_path.clone());
+        let (part, mut received) = tokio::task::spawn_blocking(move || executable.open_part(&path))
+            .await
+            .map_err(|e| ExecutableError::Download(e.to_string()))??;
+        let mut part = tokio::fs::File::from_std(part);
+        let client = reqwest::Client::builder()
+            .connect_timeout(STALL_TIMEOUT)
+            .build()
+            .map_err(|e| ExecutableError::Download(e.to_string()))?;
+        let pb = self.progress_bar()?;
+        pb.set_position(received.len);
+        let mut last_error = None;
+
+        for url_prefix in &self.servers {
+            let url = format!("{}{}", url_prefix, self.file_name_public());
+
+            let mut idle_attempts = 0;
+            while received.len < self.size && idle_attempts < MAX_IDLE_ATTEMPTS {
+                let before = received.len;
+                match self
+                    .fetch_async(&client, &url, &mut part, &mut received, &pb)
+                    .await
+                {
+                    Ok(()) => {}
+                    Err(ExecutableError::Download(e)) => last_error = Some(e),
+                    Err(e) => return Err(e),
                 }
+                idle_attempts = if received.len == before {
+                    idle_attempts + 1
+                } else {
+                    0
+                };
+            }
+            if received.len < self.size {
+                continue;
+            }
+
+            if received.sha256() == self.sha256 {
+                pb.finish_with_message("Download complete");
+                drop(part);
+                let path = temp_dir.join(self.file_name());
+                tokio::fs::rename(&part_path, &path).await?;
+                Self::make_executable(&path)?;
+                return Ok(path);
             }
+            last_error = Some("SHA256 mismatch".to_string());
+            part.set_len(0).await?;
+            received = Received::new();
+            pb.set_position(0);
         }
 
+        pb.abandon();
         Err(ExecutableError::Download(last_error.unwrap_or_else(|| {
             "All download attempts failed".to_string()
         })))
     }
+
+    /// Non-blocking variant of `fetch`, waiting at most `STALL_TIMEOUT` for each chunk.
+    #[cfg(feature = "async")]
+    async fn fetch_async(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        part: &mut tokio::fs::File,
+        received: &mut Received,
+        pb: &ProgressBar,
+    ) -> Result<(), ExecutableError> {
+        use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+        async fn stalling<T, E: ToString>(
+            future: impl std::future::Future<Output = Result<T, E>>,
+        ) -> Result<T, ExecutableError> {
+            match tokio::time::timeout(STALL_TIMEOUT, future).await {
+                Ok(result) => result.map_err(|e| ExecutableError::Download(e.to_string())),
+                Err(_) => Err(ExecutableError::Download(format!(
+                    "No data received for {:?}",
+                    STALL_TIMEOUT
+                ))),
+            }
+        }
+
+        if let Some(path) = Self::local_path(url) {
+            let local_error =
+                |e: io::Error| ExecutableError::Download(format!("{}: {}", path.display(), e));
+            let mut source = tokio::fs::File::open(&path).await.map_err(local_error)?;
+            source
+                .seek(SeekFrom::Start(received.len))
+                .await
+                .map_err(local_error)?;
+            let mut buffer = vec![0; CHUNK_SIZE];
+            while received.len < self.size {
+                let bytes_read = source.read(&mut buffer).await.map_err(local_error)?;
+                if bytes_read == 0 {
+                    break;
+                }
+                let chunk = self.missing(received, &buffer[..bytes_read]);
+                part.write_all(chunk).await?;
+                received.update(chunk);
+                pb.inc(chunk.len() as u64);
+            }
+        } else {
+            let mut request = client.get(url);
+            if received.len > 0 {
+                request = request.header(RANGE, format!("bytes={}-", received.len));
+            }
+            let mut response = stalling(request.send()).await?;
+            if !Self::resumes(response.status(), response.headers(), received.len)? {
+                part.set_len(0).await?;
+                *received = Received::new();
+                pb.set_position(0);
+            }
+            while received.len < self.size {
+                let Some(chunk) = stalling(response.chunk()).await? else {
+                    break;
+                };
+                let chunk = self.missing(received, &chunk);
+                part.write_all(chunk).await?;
+                received.update(chunk);
+                pb.inc(chunk.len() as u64);
+            }
+        }
+        part.flush().await?;
+        self.check_complete(received)
+    }
 }
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use crate::models::{requests, responses};
+
+/// One flavor as substitution rules, applied left to right with the longest match first.
+///
+/// Replaced text is never substituted again, so rules like `a -> b` and `b -> a`
+/// swap symbols instead of chaining.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Substitution {
+    rules: Vec<(String, String)>,
+}
+
+impl Substitution {
+    pub fn new(map: &HashMap<String, String>) -> Self {
+        Self::from_rules(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+
+    fn from_rules(mut rules: Vec<(String, String)>) -> Self {
+        rules.retain(|(from, _)| !from.is_empty());
+        // longest first, ties in a fixed order so the result does not depend on hashing
+        rules.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.cmp(b)));
+        rules.dedup_by(|a, b| a.0 == b.0);
+        Self { rules }
+    }
+
+    /// The reverse direction. When several symbols map to the same replacement,
+    /// the smallest one is restored.
+    pub fn inverse(&self) -> Self {
+        let rules: Vec<(String, String)> = self
+            .rules
+            .iter()
+            .map(|(from, to)| (to.clone(), from.clone()))
+            .collect();
+        Self::from_rules(rules)
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            match self
+                .rules
+                .iter()
+                .find(|(from, _)| rest.starts_with(from.as_str()))
+            {
+                Some((from, to)) => {
+                    out.push_str(to);
+                    rest = &rest[from.len()..];
+                }
+                None => {
+                    out.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Flavors of a request which are provided by the `IpaFlavor` trait and
+/// therefore applied on the client instead of by goruut.
+#[derive(Debug, Clone, Default)]
+pub struct Flavors {
+    passes: Vec<Substitution>,
+}
+
+impl Flavors {
+    /// Take the flavors known to `available` out of `req.ipa_flavors`, the
+    /// remaining names are left for goruut.
+    pub fn select(
+        available: &HashMap<String, HashMap<String, String>>,
+        req: &mut requests::PhonemizeSentence,
+    ) -> Self {
+        let mut passes = Vec::new();
+        req.ipa_flavors.retain(|name| match available.get(name) {
+            Some(map) => {
+                passes.push(Substitution::new(map));
+                false
+            }
+            None => true,
+        });
+        Self { passes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Rewrite the phonetic output of every word, flavors in request order.
+    pub fn apply(&self, resp: &mut responses::PhonemizeSentence) {
+        for word in &mut resp.words {
+            for pass in &self.passes {
+                word.phonetic = pass.apply(&word.phonetic);
+            }
+        }
+    }
+
+    /// Turn flavored IPA back into what goruut expects, for reverse requests.
+    pub fn unapply(&self, text: &str) -> String {
+        self.passes
+            .iter()
+            .rev()
+            .fold(text.to_string(), |text, pass| pass.inverse().apply(&text))
+    }
+}
@@ -1,8 +1,85 @@
+use once_cell::sync::Lazy;
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
 
+use super::release::get_releases;
+
+/// Registry shared by request validation.
+static LANGUAGES: Lazy<Languages> = Lazy::new(Languages::new);
+
+/// Number of "did you mean" suggestions offered for an unknown language.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Languages each goruut release added, oldest release first and one entry per
+/// version of `get_releases`. The oldest release supports every language of
+/// the registry not listed for a newer one.
+const ADDED_IN: &[(&str, &[&str])] = &[("v0.6.3", &[]), ("v0.7.0", &[]), ("v0.8.0", &[])];
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub struct LanguageError {
+    pub input: String,
+    /// Closest known languages, best match first.
+    pub suggestions: Vec<String>,
+}
+
+impl fmt::Display for LanguageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown language \"{}\"", self.input)?;
+        match self.suggestions.as_slice() {
+            [] => Ok(()),
+            [one] => write!(f, ", did you mean {}?", one),
+            more => write!(f, ", did you mean one of {}?", more.join(", ")),
+        }
+    }
+}
+
+/// A language goruut knows, by its goruut name (e.g. `English`, `EnglishBritish`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Language {
+    name: String,
+    code: Option<String>,
+}
+
+impl Language {
+    /// Resolve an ISO 639 code, English name or dialect name, ignoring case,
+    /// spaces, dashes and underscores.
+    pub fn parse(value: &str) -> Result<Self, LanguageError> {
+        LANGUAGES.resolve(value)
+    }
+
+    /// The name goruut expects in requests.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// ISO 639 code, `None` for dialects without one.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl FromStr for Language {
+    type Err = LanguageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// The languages goruut knows, see `Languages::for_release` for those of one release.
 pub struct Languages {
     languages_iso639: HashMap<String, String>,
     languages_non_iso639: Vec<String>,
+    // normalized name -> language, for `resolve`
+    by_name: HashMap<String, Language>,
 }
 
 impl Languages {
@@ -157,10 +234,57 @@ impl Languages {
             "MinnanHokkien2".to_string(),
         ];
 
-        Self {
+        Self::indexed(languages_iso639, languages_non_iso639)
+    }
+
+    /// Languages supported by a goruut release, `None` for releases this crate
+    /// does not know. `version` may be a prefix like `v0.7`, the newest
+    /// matching release is used.
+    pub fn for_release(version: &str) -> Option<Self> {
+        let releases = get_releases();
+        let version = releases
+            .iter()
+            .map(|r| r.version.as_str())
+            .find(|v| v.starts_with(version))?;
+        let newer = ADDED_IN
+            .iter()
+            .skip_while(|(v, _)| *v != version)
+            .skip(1)
+            .flat_map(|(_, added)| added.iter())
+            .collect::<Vec<_>>();
+
+        let all = Self::new();
+        let supported = |name: &String| !newer.iter().any(|n| *n == name);
+        let iso639 = all
+            .languages_iso639
+            .into_iter()
+            .filter(|(_, name)| supported(name))
+            .collect();
+        let non_iso639 = all
+            .languages_non_iso639
+            .into_iter()
+            .filter(supported)
+            .collect();
+        Some(Self::indexed(iso639, non_iso639))
+    }
+
+    fn indexed(
+        languages_iso639: HashMap<String, String>,
+        languages_non_iso639: Vec<String>,
+    ) -> Self {
+        let mut languages = Self {
             languages_iso639,
             languages_non_iso639,
+            by_name: HashMap::new(),
+        };
+        // In name order, the first spelling of a normalized name wins
+        for language in languages.languages() {
+            languages
+                .by_name
+                .entry(normalize(&language.name))
+                .or_insert(language);
         }
+        languages
     }
 
     pub fn get_supported_languages(&self) -> Vec<String> {
@@ -176,6 +300,57 @@ impl Languages {
         languages
     }
 
+    /// Every language of the registry, sorted by name.
+    pub fn languages(&self) -> Vec<Language> {
+        let mut languages: Vec<Language> = self
+            .languages_iso639
+            .iter()
+            .map(|(code, name)| Language {
+                name: name.clone(),
+                code: Some(code.clone()),
+            })
+            .chain(self.languages_non_iso639.iter().map(|name| Language {
+                name: name.clone(),
+                code: None,
+            }))
+            .collect();
+        languages.sort();
+        languages
+    }
+
+    /// Resolve an ISO 639 code, English name or dialect name to a `Language`.
+    pub fn resolve(&self, value: &str) -> Result<Language, LanguageError> {
+        let key = normalize(value);
+        if let Some((code, name)) = self.languages_iso639.get_key_value(key.as_str()) {
+            return Ok(Language {
+                name: name.clone(),
+                code: Some(code.clone()),
+            });
+        }
+
+        if let Some(language) = self.by_name.get(&key) {
+            return Ok(language.clone());
+        }
+
+        // Suggest names within a few edits, closest first
+        let max_distance = (key.chars().count() / 3).max(2);
+        let mut candidates: Vec<(usize, &str)> = self
+            .by_name
+            .iter()
+            .map(|(name, l)| (levenshtein(&key, name), l.name.as_str()))
+            .filter(|&(d, _)| d <= max_distance)
+            .collect();
+        candidates.sort();
+        Err(LanguageError {
+            input: value.to_string(),
+            suggestions: candidates
+                .into_iter()
+                .take(MAX_SUGGESTIONS)
+                .map(|(_, name)| name.to_string())
+                .collect(),
+        })
+    }
+
     pub fn get(&self, value: &str) -> Option<String> {
         if value.len() == 2 || value.len() == 3 {
             self.languages_iso639.get(value).cloned()
@@ -184,3 +359,34 @@ impl Languages {
         }
     }
 }
+
+impl Default for Languages {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let next = (row[j + 1] + 1)
+                .min(row[j] + 1)
+                .min(diagonal + usize::from(ca != cb));
+            diagonal = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,65 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::models::responses::{PhonemizeSentenceWord as Word, WordOffsets};
+
+/// A character of the folded sentence and where its source character starts.
+struct Folded {
+    c: char,
+    byte: usize,
+    char: usize,
+}
+
+/// Case fold and decompose so that NFC/NFD spellings and case differences match.
+fn fold(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars().flat_map(char::to_lowercase).nfd()
+}
+
+/// Set `offsets` of every word to where its `clean_word` occurs in `sentence`.
+///
+/// Words are located in order, each search starts after the previous match, so
+/// punctuation and text goruut dropped are skipped over. Words which cannot be
+/// found keep `offsets` at `None` and do not move the search position.
+pub(crate) fn assign(sentence: &str, words: &mut [Word]) {
+    let mut folded: Vec<Folded> = Vec::with_capacity(sentence.len());
+    // byte and char position of the end of the sentence, closes the last span
+    let mut end = (sentence.len(), 0);
+    for (char, (byte, c)) in sentence.char_indices().enumerate() {
+        folded.extend(fold(&sentence[byte..byte + c.len_utf8()]).map(|c| Folded { c, byte, char }));
+        end.1 = char + 1;
+    }
+
+    let mut cursor = 0;
+    for word in words.iter_mut() {
+        word.offsets = None;
+        let needle: Vec<char> = fold(&word.clean_word).collect();
+        if needle.is_empty() || needle.len() > folded.len() {
+            continue;
+        }
+        let found = (cursor..=folded.len() - needle.len()).find(|&i| {
+            folded[i..i + needle.len()]
+                .iter()
+                .zip(&needle)
+                .all(|(f, c)| f.c == *c)
+        });
+        let Some(start) = found else {
+            continue;
+        };
+
+        // extend to the end of the last source character, it may have
+        // folded into more characters than the needle covers
+        let last = &folded[start + needle.len() - 1];
+        let mut next = start + needle.len();
+        while next < folded.len() && folded[next].char == last.char {
+            next += 1;
+        }
+        let (byte_end, char_end) = folded.get(next).map_or(end, |f| (f.byte, f.char));
+
+        word.offsets = Some(WordOffsets {
+            byte_start: folded[start].byte,
+            byte_end,
+            char_start: folded[start].char,
+            char_end,
+        });
+        cursor = next;
+    }
+}
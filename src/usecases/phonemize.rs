@@ -1,9 +1,15 @@
+use super::dict;
+use super::pipeline::Pipeline;
 use super::rustruut::{Goruut, RustruutError};
+use super::word_cache::WordCacheStats;
 use crate::di::DependencyInjection;
 use crate::interfaces::{Api, DictGetter, Folder, IpaFlavor, PolicyMaxWords, Version};
 use crate::models::{requests, responses};
+use crate::options::PhonemizerOptions;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 // State enum: either we have a ready Goruut or a stored error from constructor
 enum GoruutState<P, I, D, A, F, V>
@@ -25,10 +31,18 @@ pub trait PhonemizeUsecase {
         &self,
         req: requests::PhonemizeSentence,
     ) -> Result<responses::PhonemizeSentence, RustruutError>;
+
+    /// Phonemize many sentences, results are returned in input order.
+    fn sentences(
+        &self,
+        reqs: Vec<requests::PhonemizeSentence>,
+    ) -> Vec<Result<responses::PhonemizeSentence, RustruutError>> {
+        reqs.into_iter().map(|req| self.sentence(req)).collect()
+    }
 }
 
 /// A concrete phonemize usecase implementation.
-/// Generic over the DI traits, keeps them around for orchestration.
+/// Generic over the DI traits of the goruut it orchestrates.
 pub struct PhonemizeUsecaseImpl<P, I, D, A, F, V>
 where
     P: PolicyMaxWords,
@@ -38,12 +52,7 @@ where
     F: Folder,
     V: Version,
 {
-    policy: P,
-    ipa: I,
-    dict_getter: D,
-    api: A,
-    version: V,
-    maxwrds: usize,
+    pipeline: Pipeline,
     state: Arc<GoruutState<P, I, D, A, F, V>>,
 }
 
@@ -58,6 +67,14 @@ where
 {
     /// Construct from DI container.
     pub fn new(di: DependencyInjection<P, I, D, A, F, V>) -> Self {
+        Self::with_options(di, PhonemizerOptions::default())
+    }
+
+    /// Construct from DI container with explicit runtime options.
+    pub fn with_options(
+        di: DependencyInjection<P, I, D, A, F, V>,
+        mut options: PhonemizerOptions,
+    ) -> Self {
         let maxwrds = di.policy.get_policy_max_words();
         let models = HashMap::new();
 
@@ -71,28 +88,34 @@ where
             version_str.as_deref()
         };
 
-        let policy = di.policy.clone();
-        let ipa = di.ipa.clone();
-        let dict_getter = di.dict_getter.clone();
-        let api = di.api.clone();
-        let version_provider = di.version.clone();
+        // Dictionaries provided by the DictGetter, applied along with the user lexicon
+        let dictionaries = dict::add_dictionaries(&di.dict_getter, &mut options.lexicon);
+        let mut pipeline = Pipeline::new(maxwrds, di.ipa.get_ipa_flavors(), options);
 
-        let goruut_result = Goruut::new(di, version, folder_dir.as_deref(), None, models);
+        let goruut_result = dictionaries.map_err(RustruutError::from).and_then(|()| {
+            Goruut::with_options(
+                di,
+                version,
+                folder_dir.as_deref(),
+                models,
+                pipeline.options(),
+            )
+        });
 
         let state = match goruut_result {
-            Ok(g) => Arc::new(GoruutState::Ready(Arc::new(g))),
+            Ok(g) => {
+                pipeline.open_cache(g.engine_id(), &api_path, folder_dir.as_deref());
+                Arc::new(GoruutState::Ready(Arc::new(g)))
+            }
             Err(e) => Arc::new(GoruutState::Failed(Arc::new(e))),
         };
 
-        Self {
-            policy,
-            ipa,
-            dict_getter,
-            api,
-            version: version_provider,
-            maxwrds,
-            state,
-        }
+        Self { pipeline, state }
+    }
+
+    /// Counters of the word cache, `None` when it is disabled.
+    pub fn word_cache_stats(&self) -> Option<WordCacheStats> {
+        self.pipeline.word_cache_stats()
     }
 }
 
@@ -107,17 +130,56 @@ where
 {
     fn sentence(
         &self,
-        mut req: requests::PhonemizeSentence,
+        req: requests::PhonemizeSentence,
     ) -> Result<responses::PhonemizeSentence, RustruutError> {
-        req.init();
-
-        match &*self.state {
-            GoruutState::Ready(g) => g.phonemize(req),
-            GoruutState::Failed(err) => Err(RustruutError::Generic(format!(
-                "goruut not available: {}",
-                err
-            ))),
+        let mut job = self.pipeline.begin(req)?;
+        let g = match &*self.state {
+            GoruutState::Ready(g) => g,
+            GoruutState::Failed(err) => {
+                return Err(RustruutError::Generic(format!(
+                    "goruut not available: {}",
+                    err
+                )))
+            }
+        };
+        while let Some(req) = job.request() {
+            job.respond(g.phonemize(req)?);
         }
+        job.finish()
+    }
+
+    fn sentences(
+        &self,
+        reqs: Vec<requests::PhonemizeSentence>,
+    ) -> Vec<Result<responses::PhonemizeSentence, RustruutError>> {
+        let total = reqs.len();
+        let workers = self.pipeline.options().concurrency.clamp(1, total.max(1));
+
+        // Workers pull the next request index, so slow sentences don't stall a fixed slice
+        let queue: Vec<Mutex<Option<requests::PhonemizeSentence>>> =
+            reqs.into_iter().map(|r| Mutex::new(Some(r))).collect();
+        let results: Vec<Mutex<Option<Result<responses::PhonemizeSentence, RustruutError>>>> =
+            (0..total).map(|_| Mutex::new(None)).collect();
+        let next = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let idx = next.fetch_add(1, Ordering::SeqCst);
+                    if idx >= total {
+                        break;
+                    }
+                    let req = queue[idx].lock().unwrap().take().unwrap();
+                    let result = self.sentence(req);
+                    *results[idx].lock().unwrap() = Some(result);
+                });
+            }
+        });
+
+        results
+            .into_iter()
+            .map(|r| r.into_inner().unwrap().unwrap())
+            .collect()
     }
 }
 
@@ -0,0 +1,361 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+use super::cache::DiskCache;
+use super::chunking::{self, Chunk};
+use super::ipa_flavor::Flavors;
+use super::offsets;
+use super::rustruut::{writeable_dir, RustruutError};
+use super::word_cache::{WordCache, WordCacheStats, WordKey};
+use crate::lexicon::Lexicon;
+use crate::models::requests::PhonemizeSentence as Req;
+use crate::models::responses::{PhonemizeSentence as Resp, PhonemizeSentenceWord as Word};
+use crate::options::PhonemizerOptions;
+
+/// Request handling shared by `Phonemizer` and `AsyncPhonemizer`, everything
+/// but talking to goruut: the word limit, chunking, the user lexicon, both
+/// caches, client side flavors and word offsets.
+///
+/// `begin` turns a request into a `Job` which hands out the goruut requests it
+/// needs one at a time. The front end sends them, blocking or async, feeds the
+/// responses back through `Job::respond` and collects the result with `Job::finish`.
+pub struct Pipeline {
+    maxwrds: usize,
+    // flavor name -> symbol substitutions, from the IpaFlavor provider
+    flavors: HashMap<String, HashMap<String, String>>,
+    options: PhonemizerOptions,
+    cache: Option<DiskCache>,
+    word_cache: Option<WordCache>,
+}
+
+impl Pipeline {
+    pub fn new(
+        maxwrds: usize,
+        flavors: HashMap<String, HashMap<String, String>>,
+        options: PhonemizerOptions,
+    ) -> Self {
+        let word_cache = match options.word_cache_capacity {
+            0 => None,
+            capacity => Some(WordCache::new(capacity)),
+        };
+        Self {
+            maxwrds,
+            flavors,
+            options,
+            cache: None,
+            word_cache,
+        }
+    }
+
+    /// Open the disk cache when `PhonemizerOptions::disk_cache` asks for it.
+    ///
+    /// `engine_id` is what results depend on, `None` for goruut behind the API
+    /// at `api_path`. The cache is an optimization, a folder that cannot be used
+    /// just leaves it disabled.
+    pub fn open_cache(
+        &mut self,
+        engine_id: Option<&str>,
+        api_path: &str,
+        folder_dir: Option<&str>,
+    ) {
+        let Some(cache_options) = &self.options.disk_cache else {
+            return;
+        };
+        let mut engine_version = match engine_id {
+            Some(v) => v.to_string(),
+            None => format!("api-{:x}", Sha256::digest(api_path.as_bytes())),
+        };
+        // Results depend on the lexicon too, a changed one starts a fresh cache
+        if let Some(lexicon) = self.lexicon() {
+            engine_version = format!(
+                "{}-lexicon-{}",
+                engine_version,
+                &lexicon.fingerprint()[..16]
+            );
+        }
+        self.cache = writeable_dir(folder_dir)
+            .ok()
+            .and_then(|dir| DiskCache::open(&dir, &engine_version, cache_options.clone()).ok());
+    }
+
+    pub fn options(&self) -> &PhonemizerOptions {
+        &self.options
+    }
+
+    /// Counters of the word cache, `None` when it is disabled.
+    pub fn word_cache_stats(&self) -> Option<WordCacheStats> {
+        self.word_cache.as_ref().map(|c| c.stats())
+    }
+
+    fn lexicon(&self) -> Option<&Lexicon> {
+        self.options.lexicon.as_ref().filter(|l| !l.is_empty())
+    }
+
+    /// Validate the request and look it up in the disk cache.
+    ///
+    /// Fails with `RustruutError::WordLimitExceeded` when the sentence is too
+    /// long and neither chunking nor `raw_word_limit` is enabled, and with
+    /// `RustruutError::Language` for unknown languages unless
+    /// `allow_unknown_languages` is set.
+    pub fn begin(&self, mut req: Req) -> Result<Job<'_>, RustruutError> {
+        if self.options.allow_unknown_languages {
+            req.init_lenient();
+        } else {
+            req.init()?;
+        }
+        let sentence = req.sentence.clone();
+        let reverse = req.is_reverse;
+
+        // Flavors from the IpaFlavor provider are applied here, goruut never sees them
+        let flavors = Flavors::select(&self.flavors, &mut req);
+        if reverse {
+            req.sentence = flavors.unapply(&req.sentence);
+        }
+
+        let words = chunking::word_count(&req.sentence);
+        let chunked = self.options.chunking && words > self.maxwrds;
+        if !self.options.raw_word_limit && !chunked && words > self.maxwrds {
+            return Err(RustruutError::WordLimitExceeded {
+                limit: self.maxwrds,
+                actual: words,
+            });
+        }
+
+        let mut job = Job {
+            pipeline: self,
+            sentence,
+            reverse,
+            flavors,
+            words,
+            split_sentences: req.split_sentences,
+            key: None,
+            cached: None,
+            chunks: None,
+            reqs: Vec::new(),
+            part: None,
+            pending: None,
+            responses: Vec::new(),
+        };
+        if let Some(resp) = self.cache.as_ref().and_then(|c| c.get(&req)) {
+            job.cached = Some(resp);
+            return Ok(job);
+        }
+        job.key = self.cache.as_ref().map(|_| req.clone());
+
+        if chunked {
+            let chunks = chunking::split(&req.sentence, self.maxwrds);
+            job.reqs = chunks
+                .iter()
+                .map(|chunk| Req {
+                    sentence: chunk.text.clone(),
+                    ..req.clone()
+                })
+                .collect();
+            job.chunks = Some(chunks);
+        } else {
+            job.reqs = vec![req];
+        }
+        Ok(job)
+    }
+}
+
+/// One request in progress, see `Pipeline`.
+///
+/// Chunks are phonemized in order, each one takes one or two goruut requests.
+pub struct Job<'a> {
+    pipeline: &'a Pipeline,
+    // the sentence as given, for offsets
+    sentence: String,
+    reverse: bool,
+    flavors: Flavors,
+    words: usize,
+    split_sentences: bool,
+    // disk cache key, `None` without a disk cache
+    key: Option<Req>,
+    cached: Option<Resp>,
+    // `None` unless the sentence was split
+    chunks: Option<Vec<Chunk>>,
+    // one request per chunk, `responses` holds the finished ones
+    reqs: Vec<Req>,
+    part: Option<Part>,
+    // sent for `part` and not answered yet
+    pending: Option<Req>,
+    responses: Vec<Resp>,
+}
+
+impl Job<'_> {
+    /// The next request to send to goruut, `None` once nothing is left to ask.
+    pub fn request(&mut self) -> Option<Req> {
+        while self.pending.is_none() && self.responses.len() < self.reqs.len() {
+            let req = self.reqs[self.responses.len()].clone();
+            let (part, step) = Part::start(self.pipeline, req);
+            self.step(part, step);
+        }
+        self.pending.clone()
+    }
+
+    /// Goruut's answer to the request returned by `request`.
+    pub fn respond(&mut self, resp: Resp) {
+        let mut part = self.part.take().expect("a response without a request");
+        self.pending = None;
+        let step = part.respond(self.pipeline, resp);
+        self.step(part, step);
+    }
+
+    fn step(&mut self, part: Part, step: Step) {
+        match step {
+            Step::Send(req) => {
+                self.part = Some(part);
+                self.pending = Some(req);
+            }
+            Step::Done(mut resp) => {
+                if let Some(lexicon) = self.pipeline.lexicon() {
+                    lexicon.apply(&part.req, &mut resp);
+                }
+                self.responses.push(resp);
+            }
+        }
+    }
+
+    /// The response to the request, once `request` returned `None`.
+    pub fn finish(self) -> Result<Resp, RustruutError> {
+        let pipeline = self.pipeline;
+        let mut resp = match self.cached {
+            Some(resp) => resp,
+            None => {
+                let mut responses = self.responses;
+                let resp = match &self.chunks {
+                    Some(chunks) => chunking::stitch(chunks, responses, self.split_sentences),
+                    None => responses.pop().expect("a finished job"),
+                };
+
+                // goruut may count words differently than the pre-check in `begin`
+                if resp.error_word_limit_exceeded && !pipeline.options.raw_word_limit {
+                    return Err(RustruutError::WordLimitExceeded {
+                        limit: pipeline.maxwrds,
+                        actual: self.words,
+                    });
+                }
+                if let (Some(cache), Some(key)) = (&pipeline.cache, self.key) {
+                    if !resp.error_word_limit_exceeded {
+                        let _ = cache.put(&key, &resp);
+                    }
+                }
+                resp
+            }
+        };
+
+        if !self.reverse {
+            self.flavors.apply(&mut resp);
+        }
+        offsets::assign(&self.sentence, &mut resp.words);
+        Ok(resp)
+    }
+}
+
+enum Step {
+    Send(Req),
+    Done(Resp),
+}
+
+/// One chunk, answering words overridden by the lexicon or already in the
+/// word cache locally.
+///
+/// With the word cache only the remaining words are sent. Without it goruut
+/// gets the whole chunk, unless every word is overridden, and the lexicon is
+/// applied to its response.
+struct Part {
+    req: Req,
+    tokens: Vec<String>,
+    words: Vec<Option<Word>>,
+    missing: Vec<usize>,
+    // whether the whole chunk was sent rather than the missing words
+    whole: bool,
+}
+
+impl Part {
+    fn start(pipeline: &Pipeline, req: Req) -> (Self, Step) {
+        let mut part = Self {
+            req,
+            tokens: Vec::new(),
+            words: Vec::new(),
+            missing: Vec::new(),
+            whole: true,
+        };
+        let lexicon = pipeline.lexicon();
+        if pipeline.word_cache.is_none() && lexicon.is_none() {
+            let step = Step::Send(part.req.clone());
+            return (part, step);
+        }
+
+        let req = &part.req;
+        part.tokens = req
+            .sentence
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        part.words = part
+            .tokens
+            .iter()
+            .map(|t| {
+                lexicon.and_then(|l| l.short_circuit(req, t)).or_else(|| {
+                    let cache = pipeline.word_cache.as_ref()?;
+                    cache.get(&WordKey::new(req, t))
+                })
+            })
+            .collect();
+        part.missing = (0..part.tokens.len())
+            .filter(|&i| part.words[i].is_none())
+            .collect();
+
+        let step = if part.missing.is_empty() {
+            Step::Done(part.assemble())
+        } else if pipeline.word_cache.is_none() {
+            // Keep the sentence context, overridden words are replaced in the response
+            Step::Send(part.req.clone())
+        } else {
+            part.whole = false;
+            let sentence = part
+                .missing
+                .iter()
+                .map(|&i| part.tokens[i].as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            Step::Send(Req {
+                sentence,
+                ..part.req.clone()
+            })
+        };
+        (part, step)
+    }
+
+    fn respond(&mut self, pipeline: &Pipeline, resp: Resp) -> Step {
+        if self.whole || resp.error_word_limit_exceeded {
+            return Step::Done(resp);
+        }
+        if resp.words.len() != self.missing.len() {
+            // goruut split the words differently, they cannot be matched up
+            self.whole = true;
+            return Step::Send(self.req.clone());
+        }
+        for (&i, word) in self.missing.iter().zip(resp.words) {
+            if let Some(cache) = &pipeline.word_cache {
+                cache.put(WordKey::new(&self.req, &self.tokens[i]), word.clone());
+            }
+            self.words[i] = Some(word);
+        }
+        Step::Done(self.assemble())
+    }
+
+    fn assemble(&mut self) -> Resp {
+        let mut words: Vec<Word> = std::mem::take(&mut self.words)
+            .into_iter()
+            .flatten()
+            .collect();
+        chunking::sentence_flags(&mut words, self.req.split_sentences);
+        Resp {
+            words,
+            error_word_limit_exceeded: false,
+        }
+    }
+}
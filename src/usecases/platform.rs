@@ -1,3 +1,4 @@
+use std::fmt;
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -49,27 +50,29 @@ impl FromStr for OS {
     }
 }
 
-impl ToString for Architecture {
-    fn to_string(&self) -> String {
-        match self {
-            Architecture::Amd64 => "amd64".to_string(),
-            Architecture::Arm => "arm".to_string(),
-            Architecture::Arm64 => "arm64".to_string(),
-            Architecture::I386 => "386".to_string(),
-            Architecture::Riscv64 => "riscv64".to_string(),
-        }
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Architecture::Amd64 => "amd64",
+            Architecture::Arm => "arm",
+            Architecture::Arm64 => "arm64",
+            Architecture::I386 => "386",
+            Architecture::Riscv64 => "riscv64",
+        };
+        f.write_str(s)
     }
 }
 
-impl ToString for OS {
-    fn to_string(&self) -> String {
-        match self {
-            OS::Android => "android".to_string(),
-            OS::Darwin => "darwin".to_string(),
-            OS::Linux => "linux".to_string(),
-            OS::Windows => "windows".to_string(),
-            OS::Freebsd => "freebsd".to_string(),
-        }
+impl fmt::Display for OS {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            OS::Android => "android",
+            OS::Darwin => "darwin",
+            OS::Linux => "linux",
+            OS::Windows => "windows",
+            OS::Freebsd => "freebsd",
+        };
+        f.write_str(s)
     }
 }
 
@@ -108,12 +111,12 @@ impl Platform {
     }
 }
 
-impl ToString for Platform {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "Platform(OS: {}, Architecture: {})",
-            self.os.to_string(),
-            self.architecture.to_string()
+            self.os, self.architecture
         )
     }
 }
@@ -1,24 +1,28 @@
 use crate::di::DependencyInjection;
 use crate::interfaces::{Api, DictGetter, Folder, IpaFlavor, PolicyMaxWords, Version};
+use crate::options::{ClientOptions, PhonemizerOptions};
 use once_cell::sync::Lazy;
+use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
+use std::fmt;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
-use std::sync::mpsc::{channel, RecvTimeoutError};
 use std::sync::Mutex;
-use std::thread;
-use std::time::Duration;
-use tempfile::TempDir;
 use thiserror::Error;
 
+use crate::lexicon::LexiconError;
+use crate::models::pos_tag::PosTag;
 use crate::models::{requests, responses};
+use crate::transcription::ConversionError;
 
 use super::config::Config;
+use super::dict;
 use super::executable::{Executable, ExecutableError};
+use super::languages::LanguageError;
 use super::platform::Platform;
 use super::release::get_releases;
+use super::supervisor::Supervisor;
 
 #[derive(Error, Debug)]
 pub enum RustruutError {
@@ -34,6 +38,14 @@ pub enum RustruutError {
     Process(String),
     #[error("Generic error: {0}")]
     Generic(String),
+    #[error("Word limit exceeded: {actual} words, limit is {limit}")]
+    WordLimitExceeded { limit: usize, actual: usize },
+    #[error("Language error: {0}")]
+    Language(#[from] LanguageError),
+    #[error("Conversion error: {0}")]
+    Conversion(#[from] ConversionError),
+    #[error("Lexicon error: {0}")]
+    Lexicon(#[from] LexiconError),
     // Add the new variant here
     #[error("Serde JSON error: {0}")]
     SerdeJson(#[from] serde_json::Error),
@@ -43,7 +55,7 @@ pub enum RustruutError {
 pub struct Word {
     pub clean_word: String,
     pub phonetic: String,
-    pub pos_tags: Vec<String>,
+    pub pos_tags: Vec<PosTag>,
     pub pre_punct: String,
     pub post_punct: String,
     pub is_first: bool,
@@ -56,17 +68,180 @@ pub struct PhonemeResponse {
     pub separator: String,
 }
 
-impl ToString for PhonemeResponse {
-    fn to_string(&self) -> String {
-        self.words
+impl fmt::Display for PhonemeResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let words = self
+            .words
             .iter()
             .map(|w| format!("{}{}{}", w.pre_punct, w.phonetic, w.post_punct))
-            .collect::<Vec<String>>()
-            .join(&self.separator)
+            .collect::<Vec<String>>();
+        write!(f, "{}", words.join(&self.separator))
     }
 }
 
-// A global mutex used to protect downloading (critical section)
+/// Find the goruut release matching `version` (a prefix, `None` for the latest) for this platform,
+/// downloaded through the mirrors of `source`.
+pub(crate) fn find_executable(
+    version: Option<&str>,
+    source: &impl Version,
+) -> Result<(Executable, String), RustruutError> {
+    let platform = Platform::new().map_err(|e| RustruutError::Platform(e.to_string()))?;
+    let releases = get_releases();
+    let mut executable = None;
+    let mut version_found = None;
+
+    for release in releases {
+        if let Some(ver) = version {
+            if !release.version.starts_with(ver) {
+                continue;
+            }
+        }
+
+        if release.architecture == platform.architecture && release.os == platform.os {
+            let mirrors = source
+                .get_mirrors()
+                .iter()
+                .map(|mirror| format!("{}/{}/", mirror.trim_end_matches('/'), release.version));
+            let servers = if source.mirrors_only() {
+                mirrors.collect()
+            } else {
+                mirrors.chain(release.servers).collect()
+            };
+            executable = Some(Executable {
+                size: release.size,
+                sha256: release.sha256,
+                architecture: release.architecture,
+                os: release.os,
+                servers,
+            });
+            version_found = Some(release.version);
+            break;
+        }
+    }
+
+    let executable = executable
+        .ok_or_else(|| RustruutError::Platform("No executable found for platform".to_string()))?;
+    let version =
+        version_found.ok_or_else(|| RustruutError::Platform("Version not found".to_string()))?;
+    Ok((executable, version))
+}
+
+/// Environment variable naming a pre-installed goruut executable.
+pub const EXECUTABLE_ENV: &str = "RUSTRUUT_EXECUTABLE";
+
+/// Pre-installed executable from the `Folder` provider, else from `RUSTRUUT_EXECUTABLE`.
+pub(crate) fn local_executable(folder: &impl Folder) -> Option<PathBuf> {
+    match folder.get_executable_path() {
+        Some(path) => Some(PathBuf::from(path)),
+        None => std::env::var_os(EXECUTABLE_ENV)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from),
+    }
+}
+
+/// Version of the known release for this platform (and `version` prefix) the
+/// executable at `path` is a copy of.
+pub(crate) fn verify_executable(
+    path: &Path,
+    version: Option<&str>,
+) -> Result<String, RustruutError> {
+    let platform = Platform::new().map_err(|e| RustruutError::Platform(e.to_string()))?;
+    let size = std::fs::metadata(path)?.len();
+    let candidates: Vec<_> = get_releases()
+        .into_iter()
+        .filter(|r| r.architecture == platform.architecture && r.os == platform.os)
+        .filter(|r| version.is_none_or(|v| r.version.starts_with(v)))
+        .filter(|r| r.size == size)
+        .collect();
+
+    let mut hash = None;
+    for release in candidates {
+        let hash = match &hash {
+            Some(hash) => hash,
+            None => hash.insert(sha256_file(path)?),
+        };
+        if *hash == release.sha256 {
+            return Ok(release.version);
+        }
+    }
+    Err(ExecutableError::Verification(format!(
+        "{} is not a known goruut release for {}-{}",
+        path.display(),
+        platform.os,
+        platform.architecture
+    ))
+    .into())
+}
+
+fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Cache identity of an unverified executable, changes when the file does.
+pub(crate) fn executable_id(path: &Path) -> Result<String, RustruutError> {
+    let metadata = std::fs::metadata(path)?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .unwrap_or_default();
+    let id = format!(
+        "{}\t{}\t{}",
+        path.display(),
+        metadata.len(),
+        modified.as_nanos()
+    );
+    Ok(format!("local-{:x}", Sha256::digest(id.as_bytes())))
+}
+
+/// Resolve the writeable folder: `None` is the temp dir, empty is `~/.goruut`.
+pub(crate) fn writeable_dir(writeable_bin_dir: Option<&str>) -> Result<PathBuf, RustruutError> {
+    let dir = match writeable_bin_dir {
+        None => std::env::temp_dir(),
+        Some("") => {
+            let home = dirs::home_dir().ok_or_else(|| {
+                RustruutError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Home directory not found",
+                ))
+            })?;
+            let goruut_dir = home.join(".goruut");
+            std::fs::create_dir_all(&goruut_dir)?;
+            goruut_dir
+        }
+        Some(dir) => PathBuf::from(dir),
+    };
+    Ok(dir)
+}
+
+/// Apply `ClientOptions` to a blocking or async reqwest `ClientBuilder`,
+/// the two builders share the method names but no trait.
+macro_rules! configure_client {
+    ($builder:expr, $options:expr) => {{
+        let options: &$crate::options::ClientOptions = $options;
+        let builder = $builder
+            .pool_max_idle_per_host(options.pool_max_idle_per_host)
+            .pool_idle_timeout(options.pool_idle_timeout)
+            .tcp_keepalive(options.tcp_keepalive);
+        match options.timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        }
+    }};
+}
+#[cfg(feature = "async")]
+pub(crate) use configure_client;
+
+/// Build the long-lived client, connections are pooled and kept alive across requests.
+pub(crate) fn build_client(options: &ClientOptions) -> Result<Client, RustruutError> {
+    Ok(configure_client!(Client::builder(), options).build()?)
+}
+
+// A global mutex used to protect downloading within the process,
+// `Executable::exists_or_download` locks out other processes
 static DOWNLOAD_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
 pub struct Goruut<P, I, D, A, F, V>
@@ -78,14 +253,11 @@ where
     F: Folder,
     V: Version,
 {
-    policy: P,
-    ipa: I,
-    dict_getter: D,
-    version_provider: V,
-    executable: Option<Executable>,
-    platform: Option<Platform>,
     version: Option<String>,
-    process: Option<Child>,
+    // identity of a given executable, for caches when the release is unknown
+    executable_id: Option<String>,
+    process: Option<Supervisor>,
+    client: Client,
     config: Config<P, I, D, A, F, V>,
 }
 
@@ -100,222 +272,174 @@ where
 {
     fn download_critical(executable: &Executable, p: &Path) -> Result<PathBuf, RustruutError> {
         let _guard = DOWNLOAD_LOCK.lock().unwrap();
-        let executable_path = match executable.exists(p) {
-            Ok(path) => path,
-            Err(_) => executable.download(p)?,
-        };
-        return Ok(executable_path);
+        Ok(executable.exists_or_download(p)?)
     }
 
+    /// Start goruut with the default `PhonemizerOptions`.
+    ///
+    /// `_api` is unused and kept for compatibility, the API path comes from the
+    /// `Api` provider of `di`.
     pub fn new(
         di: DependencyInjection<P, I, D, A, F, V>,
         version: Option<&str>,
         writeable_bin_dir: Option<&str>,
-        api: Option<&str>,
+        _api: Option<&str>,
+        models: HashMap<String, String>,
+    ) -> Result<Self, RustruutError> {
+        Self::with_options(
+            di,
+            version,
+            writeable_bin_dir,
+            models,
+            &PhonemizerOptions::default(),
+        )
+    }
+
+    pub fn with_options(
+        di: DependencyInjection<P, I, D, A, F, V>,
+        version: Option<&str>,
+        writeable_bin_dir: Option<&str>,
         models: HashMap<String, String>,
+        options: &PhonemizerOptions,
     ) -> Result<Self, RustruutError> {
-        if di.api.get_api_path().len() != 0 {
+        let client = build_client(&options.client)?;
+
+        if !di.api.get_api_path().is_empty() {
             let config = Config::new(di.clone());
             return Ok(Self {
-                policy: di.policy.clone(),
-                ipa: di.ipa.clone(),
-                dict_getter: di.dict_getter.clone(),
-                version_provider: di.version.clone(),
-                executable: None,
-                platform: None,
                 version: None,
+                executable_id: None,
                 process: None,
-                config: config,
+                client,
+                config,
             });
         }
 
-        let platform = Platform::new().map_err(|e| RustruutError::Platform(e.to_string()))?;
-        let releases = get_releases();
-        let mut executable = None;
-        let mut version_found = None;
+        // A pre-installed executable needs no network access at all
+        if let Some(path) = local_executable(&di.folder) {
+            let version = if options.verify_executable {
+                Some(verify_executable(&path, version)?)
+            } else {
+                None
+            };
+            let mut goruut = Self::with_executable(di, &path, writeable_bin_dir, models, options)?;
+            goruut.version = version;
+            return Ok(goruut);
+        }
 
-        for release in releases {
-            if let Some(ver) = version {
-                if !release.version.starts_with(ver) {
-                    continue;
-                }
-            }
+        let (executable, version) = find_executable(version, &di.version)?;
+        let temp_dir = writeable_dir(writeable_bin_dir)?;
 
-            if release.architecture == platform.architecture && release.os == platform.os {
-                executable = Some(Executable {
-                    size: release.size,
-                    sha256: release.sha256,
-                    architecture: release.architecture,
-                    os: release.os,
-                    servers: release.servers,
-                });
-                version_found = Some(release.version);
-                break;
-            }
-        }
+        let executable_path = Self::download_critical(&executable, &temp_dir)?;
 
-        let executable = executable.ok_or_else(|| {
-            RustruutError::Platform("No executable found for platform".to_string())
-        })?;
-        let version = version_found
-            .ok_or_else(|| RustruutError::Platform("Version not found".to_string()))?;
+        let mut goruut = Self::spawn(di, &executable_path, &temp_dir, models, options, client)?;
+        goruut.version = Some(version);
+        Ok(goruut)
+    }
 
-        let temp_dir = if writeable_bin_dir == None {
-            std::env::temp_dir()
-        } else if writeable_bin_dir.as_deref().map_or(true, |s| s.is_empty()) {
-            let home = dirs::home_dir().ok_or_else(|| {
-                RustruutError::Io(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    "Home directory not found",
-                ))
-            })?;
-            let goruut_dir = home.join(".goruut");
-            std::fs::create_dir_all(&goruut_dir)?;
-            goruut_dir
-        } else {
-            PathBuf::from(writeable_bin_dir.unwrap_or(""))
-        };
+    /// Start the goruut executable at `executable_path` instead of a downloaded release.
+    ///
+    /// Config and model files are written to `writeable_bin_dir` like `new` does.
+    pub fn with_executable(
+        di: DependencyInjection<P, I, D, A, F, V>,
+        executable_path: &Path,
+        writeable_bin_dir: Option<&str>,
+        models: HashMap<String, String>,
+        options: &PhonemizerOptions,
+    ) -> Result<Self, RustruutError> {
+        let client = build_client(&options.client)?;
+        let temp_dir = writeable_dir(writeable_bin_dir)?;
+        let executable_id = executable_id(executable_path)?;
+        let mut goruut = Self::spawn(di, executable_path, &temp_dir, models, options, client)?;
+        goruut.executable_id = Some(executable_id);
+        Ok(goruut)
+    }
 
-        let executable_path = Self::download_critical(&executable, &temp_dir)?;
+    fn spawn(
+        di: DependencyInjection<P, I, D, A, F, V>,
+        executable_path: &Path,
+        temp_dir: &Path,
+        models: HashMap<String, String>,
+        options: &PhonemizerOptions,
+        client: Client,
+    ) -> Result<Self, RustruutError> {
+        // Models provided by the DictGetter, explicitly passed ones take precedence
+        let mut models = models;
+        for (lang, file) in dict::materialize(&di.dict_getter, temp_dir)? {
+            models.entry(lang).or_insert(file);
+        }
 
         let config = Config::new(di.clone());
         let config_path = temp_dir.join(format!("goruut_config_{}.json", config.get_port()));
         config.serialize(config_path.to_str().unwrap(), &models)?;
 
-        // Inside your function
-        let mut child = Command::new(executable_path)
-            .arg("--configfile")
-            .arg(config_path.to_str().unwrap())
-            .stderr(Stdio::piped()) // Capture stderr
-            .spawn()?;
-
-        // Take ownership of the child's stderr
-        let stderr = child.stderr.take().expect("Failed to capture stderr");
-
-        // Create a channel for thread communication
-        let (tx, rx) = channel();
-
-        // Spawn a thread to read stderr
-        thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines() {
-                match line {
-                    Ok(line) => {
-                        if line.contains("Serving...") {
-                            tx.send(Some(())).expect("Failed to send message");
-                        }
-                    }
-                    Err(_) => break, // Stop on read error
-                }
-            }
-            // Send None if EOF reached without finding the message
-            tx.send(None).expect("Failed to send message");
-        });
-
-        // Wait for either the message or process exit
-        let result = match rx.recv_timeout(Duration::from_secs(30)) {
-            Ok(Some(())) => {
-                // Found "Serving..." message
-                let s = Self {
-                    policy: di.policy.clone(),
-                    ipa: di.ipa.clone(),
-                    dict_getter: di.dict_getter.clone(),
-                    version_provider: di.version.clone(),
-                    executable: Some(executable),
-                    platform: Some(platform),
-                    version: Some(version),
-                    process: Some(child),
-                    config: config,
-                };
-                /*
-                        // Send one dummy request and end
-                        let url = s.config.url("tts/phonemize/sentence");
-                        let payload = "{}".to_string();
-
-                        let client = reqwest::blocking::Client::new();
-                        let _ = client.post(&url).json(&payload).send();
-
-                    // Usage - parse from empty JSON
-                    let mut req: requests::PhonemizeSentence = serde_json::from_str("{}")?;
-
-                        req.init();
-
-                        let _ = s.phonemize(req);
-                */
-                return Ok(s);
-            }
-            Ok(None) => {
-                // Stderr closed without finding the message
-                return Err(RustruutError::Process(
-                    "Process exited without serving message".into(),
-                ));
-            }
-            Err(RecvTimeoutError::Timeout) => {
-                // Timeout reached
-                return Err(RustruutError::Process(
-                    "Timeout waiting for serving message".into(),
-                ));
-            }
-            Err(RecvTimeoutError::Disconnected) => {
-                // Thread panicked or closed channel
-                return Err(RustruutError::Process(
-                    "Stderr reader thread disconnected".into(),
-                ));
-            }
-        };
-
-        // Check if process is still running
-        if let Ok(Some(status)) = child.try_wait() {
-            return Err(RustruutError::Process(format!(
-                "Process exited early with status: {}",
-                status
-            )));
-        }
+        let process = Supervisor::start(executable_path, &config_path, options.restart.clone())?;
 
         Ok(Self {
-            policy: di.policy.clone(),
-            ipa: di.ipa.clone(),
-            dict_getter: di.dict_getter.clone(),
-            version_provider: di.version.clone(),
-            executable: Some(executable),
-            platform: Some(platform),
-            version: Some(version),
-            process: Some(child),
-            config: config,
+            version: None,
+            executable_id: None,
+            process: Some(process),
+            client,
+            config,
         })
     }
 
+    /// Resolved goruut release, `None` when an external API or a given executable is used.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// What results depend on: the release, else the given executable, `None` for an external API.
+    pub(crate) fn engine_id(&self) -> Option<&str> {
+        self.version.as_deref().or(self.executable_id.as_deref())
+    }
+
+    /// Phonemize the request, transparently respawning a crashed goruut process
+    /// and retrying the request against it.
     pub fn phonemize(
         &self,
         req: requests::PhonemizeSentence,
+    ) -> Result<responses::PhonemizeSentence, RustruutError> {
+        loop {
+            let generation = self.process.as_ref().map(|p| p.generation());
+
+            match self.send(&req) {
+                Ok(resp) => {
+                    if let Some(process) = &self.process {
+                        process.mark_healthy();
+                    }
+                    return Ok(resp);
+                }
+                Err(err) => {
+                    let (Some(process), Some(generation)) = (&self.process, generation) else {
+                        return Err(err);
+                    };
+                    let connection_failed = matches!(
+                        &err,
+                        RustruutError::Http(e) if e.is_connect() || e.is_request()
+                    );
+                    if !process.recover(generation, connection_failed)? {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+
+    fn send(
+        &self,
+        req: &requests::PhonemizeSentence,
     ) -> Result<responses::PhonemizeSentence, RustruutError> {
         let url = self.config.url("tts/phonemize/sentence");
 
         let payload = serde_json::json!(req);
 
-        let client = reqwest::blocking::Client::new();
-        let response = client.post(&url).json(&payload).send()?;
+        let response = self.client.post(&url).json(&payload).send()?;
 
         let data: serde_json::Value = response.json()?;
 
-        let resp: responses::PhonemizeSentence = serde_json::from_value(data.clone())?;
+        let resp: responses::PhonemizeSentence = serde_json::from_value(data)?;
 
         Ok(resp)
     }
 }
-
-impl<P, I, D, A, F, V> Drop for Goruut<P, I, D, A, F, V>
-where
-    P: PolicyMaxWords,
-    I: IpaFlavor,
-    D: DictGetter,
-    A: Api,
-    F: Folder,
-    V: Version,
-{
-    fn drop(&mut self) {
-        if let Some(process) = &mut self.process {
-            let _ = process.kill();
-        }
-    }
-}
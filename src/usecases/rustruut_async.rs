@@ -0,0 +1,205 @@
+use crate::di::DependencyInjection;
+use crate::interfaces::{Api, DictGetter, Folder, IpaFlavor, PolicyMaxWords, Version};
+use crate::models::{requests, responses};
+use crate::options::PhonemizerOptions;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+use super::config::Config;
+use super::dict;
+use super::executable::Executable;
+use super::rustruut::{
+    configure_client, executable_id, find_executable, local_executable, verify_executable,
+    writeable_dir, RustruutError,
+};
+use super::supervisor_async::AsyncSupervisor;
+
+// An async mutex used to protect downloading within the process,
+// `Executable::exists_or_download_async` locks out other processes
+static ASYNC_DOWNLOAD_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Non-blocking counterpart of `Goruut`, all IO runs on the tokio runtime.
+pub struct AsyncGoruut<P, I, D, A, F, V>
+where
+    P: PolicyMaxWords,
+    I: IpaFlavor,
+    D: DictGetter,
+    A: Api,
+    F: Folder,
+    V: Version,
+{
+    version: Option<String>,
+    // identity of a given executable, for caches when the release is unknown
+    executable_id: Option<String>,
+    process: Option<AsyncSupervisor>,
+    client: Client,
+    config: Config<P, I, D, A, F, V>,
+}
+
+impl<P, I, D, A, F, V> AsyncGoruut<P, I, D, A, F, V>
+where
+    P: PolicyMaxWords,
+    I: IpaFlavor,
+    D: DictGetter,
+    A: Api,
+    F: Folder,
+    V: Version,
+{
+    async fn download_critical(
+        executable: &Executable,
+        p: &Path,
+    ) -> Result<PathBuf, RustruutError> {
+        let _guard = ASYNC_DOWNLOAD_LOCK.lock().await;
+        Ok(executable.exists_or_download_async(p).await?)
+    }
+
+    pub async fn new(
+        di: DependencyInjection<P, I, D, A, F, V>,
+        version: Option<&str>,
+        writeable_bin_dir: Option<&str>,
+        models: HashMap<String, String>,
+        options: &PhonemizerOptions,
+    ) -> Result<Self, RustruutError>
+    where
+        D: 'static,
+    {
+        let client = configure_client!(Client::builder(), &options.client).build()?;
+
+        if !di.api.get_api_path().is_empty() {
+            return Ok(Self {
+                version: None,
+                executable_id: None,
+                process: None,
+                client,
+                config: Config::new(di),
+            });
+        }
+
+        let dir = writeable_bin_dir.map(str::to_string);
+        let temp_dir = blocking(move || writeable_dir(dir.as_deref())).await?;
+        // A pre-installed executable needs no network access at all
+        let (executable_path, version, executable_id) = match local_executable(&di.folder) {
+            Some(path) => {
+                let (wanted, checked) = (version.map(str::to_string), path.clone());
+                let verify = options.verify_executable;
+                let (version, id) = blocking(move || {
+                    let version = match verify {
+                        true => Some(verify_executable(&checked, wanted.as_deref())?),
+                        false => None,
+                    };
+                    Ok((version, executable_id(&checked)?))
+                })
+                .await?;
+                (path, version, Some(id))
+            }
+            None => {
+                let (executable, version) = find_executable(version, &di.version)?;
+                let path = Self::download_critical(&executable, &temp_dir).await?;
+                (path, Some(version), None)
+            }
+        };
+
+        // Models provided by the DictGetter, explicitly passed ones take precedence
+        let (dict, dir) = (di.dict_getter.clone(), temp_dir.clone());
+        let loaded = blocking(move || Ok(dict::materialize(&dict, &dir)?)).await?;
+        let mut models = models;
+        for (lang, file) in loaded {
+            models.entry(lang).or_insert(file);
+        }
+
+        let config = Config::new(di);
+        let config_path = temp_dir.join(format!("goruut_config_{}.json", config.get_port()));
+        tokio::fs::write(&config_path, config.to_json(&models)?).await?;
+
+        let process =
+            AsyncSupervisor::start(&executable_path, &config_path, options.restart.clone()).await?;
+
+        Ok(Self {
+            version,
+            executable_id,
+            process: Some(process),
+            client,
+            config,
+        })
+    }
+
+    /// Resolved goruut release, `None` when an external API is used.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// What results depend on: the release, else the given executable, `None` for an external API.
+    pub(crate) fn engine_id(&self) -> Option<&str> {
+        self.version.as_deref().or(self.executable_id.as_deref())
+    }
+
+    /// Process id of the spawned goruut executable.
+    pub async fn id(&self) -> Option<u32> {
+        match &self.process {
+            Some(process) => process.id().await,
+            None => None,
+        }
+    }
+
+    /// Phonemize the request, transparently respawning a crashed goruut process
+    /// and retrying the request against it.
+    pub async fn phonemize(
+        &self,
+        req: requests::PhonemizeSentence,
+    ) -> Result<responses::PhonemizeSentence, RustruutError> {
+        loop {
+            let generation = match &self.process {
+                Some(process) => Some(process.generation().await),
+                None => None,
+            };
+
+            match self.send(&req).await {
+                Ok(resp) => {
+                    if let Some(process) = &self.process {
+                        process.mark_healthy().await;
+                    }
+                    return Ok(resp);
+                }
+                Err(err) => {
+                    let (Some(process), Some(generation)) = (&self.process, generation) else {
+                        return Err(err);
+                    };
+                    let connection_failed = matches!(
+                        &err,
+                        RustruutError::Http(e) if e.is_connect() || e.is_request()
+                    );
+                    if !process.recover(generation, connection_failed).await? {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        req: &requests::PhonemizeSentence,
+    ) -> Result<responses::PhonemizeSentence, RustruutError> {
+        let url = self.config.url("tts/phonemize/sentence");
+
+        let response = self.client.post(&url).json(req).send().await?;
+
+        let resp: responses::PhonemizeSentence = response.json().await?;
+
+        Ok(resp)
+    }
+}
+
+/// Run blocking filesystem work off the executor threads.
+async fn blocking<T, F>(f: F) -> Result<T, RustruutError>
+where
+    F: FnOnce() -> Result<T, RustruutError> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| RustruutError::Generic(e.to_string()))?
+}
@@ -0,0 +1,202 @@
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::options::RestartPolicy;
+
+use super::rustruut::RustruutError;
+
+/// How long to wait for the "Serving..." line after spawning the executable.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+struct SupervisorState {
+    child: Child,
+    // bumped after every respawn so concurrent callers restart only once
+    generation: u64,
+    // consecutive restarts since the last successful request
+    restarts: usize,
+}
+
+/// Supervisor keeps a goruut child process alive.
+///
+/// A dead child is detected through `Child::try_wait` or failed requests and
+/// respawned with the same executable and config file, obeying the `RestartPolicy`.
+pub struct Supervisor {
+    executable_path: PathBuf,
+    config_path: PathBuf,
+    policy: RestartPolicy,
+    state: Mutex<SupervisorState>,
+    // held for a whole recovery, so only one caller respawns at a time
+    restarting: Mutex<()>,
+}
+
+impl Supervisor {
+    /// Spawn the executable and wait until it reports it is serving.
+    pub fn start(
+        executable_path: &Path,
+        config_path: &Path,
+        policy: RestartPolicy,
+    ) -> Result<Self, RustruutError> {
+        let child = Self::spawn(executable_path, config_path)?;
+        Ok(Self {
+            executable_path: executable_path.to_path_buf(),
+            config_path: config_path.to_path_buf(),
+            policy,
+            state: Mutex::new(SupervisorState {
+                child,
+                generation: 0,
+                restarts: 0,
+            }),
+            restarting: Mutex::new(()),
+        })
+    }
+
+    /// Spawn one child process and block until "Serving..." appears on its stderr.
+    pub fn spawn(executable_path: &Path, config_path: &Path) -> Result<Child, RustruutError> {
+        let mut child = Command::new(executable_path)
+            .arg("--configfile")
+            .arg(config_path)
+            .stderr(Stdio::piped()) // Capture stderr
+            .spawn()?;
+
+        // Take ownership of the child's stderr
+        let stderr = child.stderr.take().expect("Failed to capture stderr");
+
+        // Create a channel for thread communication
+        let (tx, rx) = channel();
+
+        // Spawn a thread to read stderr, it keeps draining the pipe for the child's lifetime
+        thread::spawn(move || {
+            let reader = BufReader::new(stderr);
+            for line in reader.lines() {
+                match line {
+                    Ok(line) => {
+                        if line.contains("Serving...") {
+                            let _ = tx.send(Some(()));
+                        }
+                    }
+                    Err(_) => break, // Stop on read error
+                }
+            }
+            // Send None if EOF reached without finding the message
+            let _ = tx.send(None);
+        });
+
+        // Wait for either the message or process exit
+        let err = match rx.recv_timeout(STARTUP_TIMEOUT) {
+            Ok(Some(())) => return Ok(child),
+            Ok(None) => match child.try_wait() {
+                Ok(Some(status)) => {
+                    RustruutError::Process(format!("Process exited early with status: {}", status))
+                }
+                _ => RustruutError::Process("Process exited without serving message".into()),
+            },
+            Err(RecvTimeoutError::Timeout) => {
+                RustruutError::Process("Timeout waiting for serving message".into())
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                RustruutError::Process("Stderr reader thread disconnected".into())
+            }
+        };
+
+        let _ = child.kill();
+        let _ = child.wait();
+        Err(err)
+    }
+
+    /// Generation of the current child, to be passed to `recover` after a failed request.
+    pub fn generation(&self) -> u64 {
+        self.state.lock().unwrap().generation
+    }
+
+    /// Process id of the current child.
+    pub fn id(&self) -> u32 {
+        self.state.lock().unwrap().child.id()
+    }
+
+    /// Whether the current child is still running.
+    pub fn is_running(&self) -> bool {
+        matches!(self.state.lock().unwrap().child.try_wait(), Ok(None))
+    }
+
+    /// Number of consecutive restarts since the last successful request.
+    pub fn restarts(&self) -> usize {
+        self.state.lock().unwrap().restarts
+    }
+
+    /// Reset the restart budget, called after a successful request.
+    pub fn mark_healthy(&self) {
+        self.state.lock().unwrap().restarts = 0;
+    }
+
+    /// Recover after a request against child `generation` failed.
+    ///
+    /// Returns `Ok(true)` when the request should be retried, either because the
+    /// child was respawned here or by a concurrent caller. Returns `Ok(false)` when
+    /// the child is healthy and the failure was not a connection failure.
+    ///
+    /// Concurrent recoveries wait for each other, a respawn can take up to the
+    /// startup timeout. The state itself is only locked briefly, so `generation`,
+    /// `id` and the other accessors don't wait for a restart.
+    pub fn recover(&self, generation: u64, connection_failed: bool) -> Result<bool, RustruutError> {
+        let _restarting = self.restarting.lock().unwrap();
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.generation != generation {
+                return Ok(true);
+            }
+
+            let exited = !matches!(state.child.try_wait(), Ok(None));
+            if !exited && !connection_failed {
+                return Ok(false);
+            }
+
+            if !exited {
+                // Alive but not answering, replace it
+                let _ = state.child.kill();
+            }
+            let _ = state.child.wait();
+        }
+
+        loop {
+            let restarts = {
+                let mut state = self.state.lock().unwrap();
+                if state.restarts >= self.policy.max_restarts {
+                    return Err(RustruutError::Process(format!(
+                        "Restart budget exhausted after {} restarts",
+                        state.restarts
+                    )));
+                }
+                state.restarts += 1;
+                state.restarts
+            };
+            thread::sleep(self.policy.backoff(restarts));
+
+            match Self::spawn(&self.executable_path, &self.config_path) {
+                Ok(child) => {
+                    // Only recoveries bump the generation and they hold `restarting`,
+                    // so it still is the one checked above
+                    let mut state = self.state.lock().unwrap();
+                    state.child = child;
+                    state.generation += 1;
+                    return Ok(true);
+                }
+                Err(_) if restarts < self.policy.max_restarts => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        if let Ok(state) = self.state.get_mut() {
+            let _ = state.child.kill();
+            let _ = state.child.wait();
+        }
+    }
+}
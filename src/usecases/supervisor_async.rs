@@ -0,0 +1,183 @@
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::{oneshot, Mutex};
+
+use crate::options::RestartPolicy;
+
+use super::rustruut::RustruutError;
+
+/// How long to wait for the "Serving..." line after spawning the executable.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+struct SupervisorState {
+    // killed on drop
+    child: Child,
+    // bumped after every respawn so concurrent callers restart only once
+    generation: u64,
+    // consecutive restarts since the last successful request
+    restarts: usize,
+}
+
+/// Non-blocking counterpart of `Supervisor`, keeps a goruut child process alive.
+///
+/// A dead child is detected through `Child::try_wait` or failed requests and
+/// respawned with the same executable and config file, obeying the `RestartPolicy`.
+/// Backoff sleeps and startup waits run on the tokio runtime.
+pub struct AsyncSupervisor {
+    executable_path: PathBuf,
+    config_path: PathBuf,
+    policy: RestartPolicy,
+    state: Mutex<SupervisorState>,
+    // held for a whole recovery, so only one caller respawns at a time
+    restarting: Mutex<()>,
+}
+
+impl AsyncSupervisor {
+    /// Spawn the executable and wait until it reports it is serving.
+    pub async fn start(
+        executable_path: &Path,
+        config_path: &Path,
+        policy: RestartPolicy,
+    ) -> Result<Self, RustruutError> {
+        let child = Self::spawn(executable_path, config_path).await?;
+        Ok(Self {
+            executable_path: executable_path.to_path_buf(),
+            config_path: config_path.to_path_buf(),
+            policy,
+            state: Mutex::new(SupervisorState {
+                child,
+                generation: 0,
+                restarts: 0,
+            }),
+            restarting: Mutex::new(()),
+        })
+    }
+
+    /// Spawn one child process and wait without blocking until "Serving..." appears on its stderr.
+    pub async fn spawn(executable_path: &Path, config_path: &Path) -> Result<Child, RustruutError> {
+        let mut child = Command::new(executable_path)
+            .arg("--configfile")
+            .arg(config_path)
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stderr = child.stderr.take().expect("Failed to capture stderr");
+        let (tx, rx) = oneshot::channel();
+
+        // Keep draining stderr for the child's lifetime
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            let mut tx = Some(tx);
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.contains("Serving...") {
+                    if let Some(tx) = tx.take() {
+                        let _ = tx.send(());
+                    }
+                }
+            }
+        });
+
+        let err = match tokio::time::timeout(STARTUP_TIMEOUT, rx).await {
+            Ok(Ok(())) => return Ok(child),
+            Ok(Err(_)) => match child.try_wait() {
+                Ok(Some(status)) => {
+                    RustruutError::Process(format!("Process exited early with status: {}", status))
+                }
+                _ => RustruutError::Process("Process exited without serving message".into()),
+            },
+            Err(_) => RustruutError::Process("Timeout waiting for serving message".into()),
+        };
+
+        let _ = child.kill().await;
+        Err(err)
+    }
+
+    /// Generation of the current child, to be passed to `recover` after a failed request.
+    pub async fn generation(&self) -> u64 {
+        self.state.lock().await.generation
+    }
+
+    /// Process id of the current child, `None` once it has been reaped.
+    pub async fn id(&self) -> Option<u32> {
+        self.state.lock().await.child.id()
+    }
+
+    /// Whether the current child is still running.
+    pub async fn is_running(&self) -> bool {
+        matches!(self.state.lock().await.child.try_wait(), Ok(None))
+    }
+
+    /// Number of consecutive restarts since the last successful request.
+    pub async fn restarts(&self) -> usize {
+        self.state.lock().await.restarts
+    }
+
+    /// Reset the restart budget, called after a successful request.
+    pub async fn mark_healthy(&self) {
+        self.state.lock().await.restarts = 0;
+    }
+
+    /// Recover after a request against child `generation` failed.
+    ///
+    /// Returns `Ok(true)` when the request should be retried, either because the
+    /// child was respawned here or by a concurrent caller. Returns `Ok(false)` when
+    /// the child is healthy and the failure was not a connection failure.
+    ///
+    /// Like `Supervisor::recover`, concurrent recoveries wait for each other while
+    /// the state is only locked briefly.
+    pub async fn recover(
+        &self,
+        generation: u64,
+        connection_failed: bool,
+    ) -> Result<bool, RustruutError> {
+        let _restarting = self.restarting.lock().await;
+        {
+            let mut state = self.state.lock().await;
+            if state.generation != generation {
+                return Ok(true);
+            }
+
+            let exited = !matches!(state.child.try_wait(), Ok(None));
+            if !exited && !connection_failed {
+                return Ok(false);
+            }
+
+            if !exited {
+                // Alive but not answering, replace it
+                let _ = state.child.kill().await;
+            }
+        }
+
+        loop {
+            let restarts = {
+                let mut state = self.state.lock().await;
+                if state.restarts >= self.policy.max_restarts {
+                    return Err(RustruutError::Process(format!(
+                        "Restart budget exhausted after {} restarts",
+                        state.restarts
+                    )));
+                }
+                state.restarts += 1;
+                state.restarts
+            };
+            tokio::time::sleep(self.policy.backoff(restarts)).await;
+
+            match Self::spawn(&self.executable_path, &self.config_path).await {
+                Ok(child) => {
+                    // Only recoveries bump the generation and they hold `restarting`
+                    let mut state = self.state.lock().await;
+                    state.child = child;
+                    state.generation += 1;
+                    return Ok(true);
+                }
+                Err(_) if restarts < self.policy.max_restarts => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
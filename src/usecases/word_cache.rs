@@ -0,0 +1,129 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::models::{requests, responses};
+
+/// Hit/miss counters of the word cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WordCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Words currently cached.
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl WordCacheStats {
+    /// Share of lookups answered from the cache, 0 when nothing was looked up.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Everything in a request that influences how a single word is phonemized.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WordKey {
+    language: String,
+    languages: Vec<String>,
+    ipa_flavors: Vec<String>,
+    is_reverse: bool,
+    token: String,
+}
+
+impl WordKey {
+    pub fn new(req: &requests::PhonemizeSentence, token: &str) -> Self {
+        Self {
+            language: req.language.clone(),
+            languages: req.languages.clone(),
+            ipa_flavors: req.ipa_flavors.clone(),
+            is_reverse: req.is_reverse,
+            token: token.to_string(),
+        }
+    }
+}
+
+struct Lru {
+    map: HashMap<WordKey, (responses::PhonemizeSentenceWord, u64)>,
+    // last use tick -> key, the first entry is the least recently used
+    order: BTreeMap<u64, WordKey>,
+    tick: u64,
+}
+
+/// Thread-safe in-process LRU cache of single word results.
+pub struct WordCache {
+    capacity: usize,
+    lru: Mutex<Lru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl WordCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            lru: Mutex::new(Lru {
+                map: HashMap::new(),
+                order: BTreeMap::new(),
+                tick: 0,
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn get(&self, key: &WordKey) -> Option<responses::PhonemizeSentenceWord> {
+        let mut guard = self.lru.lock().unwrap();
+        let lru = &mut *guard;
+        lru.tick += 1;
+        let tick = lru.tick;
+        match lru.map.get_mut(key) {
+            Some((word, used)) => {
+                lru.order.remove(used);
+                *used = tick;
+                lru.order.insert(tick, key.clone());
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(word.clone())
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    pub fn put(&self, key: WordKey, word: responses::PhonemizeSentenceWord) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut guard = self.lru.lock().unwrap();
+        let lru = &mut *guard;
+        lru.tick += 1;
+        let tick = lru.tick;
+        if let Some((_, used)) = lru.map.insert(key.clone(), (word, tick)) {
+            lru.order.remove(&used);
+        }
+        lru.order.insert(tick, key);
+
+        while lru.map.len() > self.capacity {
+            let Some((_, oldest)) = lru.order.pop_first() else {
+                break;
+            };
+            lru.map.remove(&oldest);
+        }
+    }
+
+    pub fn stats(&self) -> WordCacheStats {
+        WordCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            len: self.lru.lock().unwrap().map.len(),
+            capacity: self.capacity,
+        }
+    }
+}
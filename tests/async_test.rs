@@ -0,0 +1,283 @@
+#![cfg(feature = "async")]
+
+use rustruut::interfaces::{Folder, PolicyMaxWords};
+use rustruut::test_util::{mock_async_phonemizer, mock_di, request, MockServer};
+use rustruut::usecases::rustruut::RustruutError;
+use rustruut::{
+    di, AsyncPhonemizer, DependencyInjection, DiskCacheOptions, Lexicon, PhonemizerOptions,
+};
+
+#[tokio::test]
+async fn async_sentence_against_api() {
+    let server = MockServer::echo();
+    let p = mock_async_phonemizer(&server, PhonemizerOptions::default()).await;
+
+    let (a, b) = tokio::join!(p.sentence(request("hello world")), p.sentence(request("bye")));
+    let (a, b) = (a.unwrap(), b.unwrap());
+    assert_eq!(a.words.len(), 2);
+    assert_eq!(a.words[1].clean_word, "world");
+    assert_eq!(b.words[0].phonetic, "bye");
+}
+
+#[derive(Clone)]
+struct TwoWordPolicy;
+impl PolicyMaxWords for TwoWordPolicy {
+    fn get_policy_max_words(&self) -> usize {
+        2
+    }
+}
+
+async fn limited(
+    server: &MockServer,
+    options: PhonemizerOptions,
+) -> AsyncPhonemizer<
+    TwoWordPolicy,
+    di::default_impls::DummyIpaFlavor,
+    di::default_impls::DummyDict,
+    di::custom_impls::CustomApi,
+    di::default_impls::DummyFolder,
+    di::default_impls::DummyVersion,
+> {
+    let mock = mock_di(server);
+    let di = DependencyInjection::with_parts(
+        TwoWordPolicy,
+        mock.ipa,
+        mock.dict_getter,
+        mock.api,
+        mock.folder,
+        mock.version,
+    );
+    AsyncPhonemizer::with_options(di, options).await
+}
+
+#[tokio::test]
+async fn word_limit_and_chunking_match_the_blocking_phonemizer() {
+    let server = MockServer::echo();
+    let p = limited(&server, PhonemizerOptions::default()).await;
+    let err = p.sentence(request("one two three")).await.unwrap_err();
+    assert!(matches!(
+        err,
+        RustruutError::WordLimitExceeded {
+            limit: 2,
+            actual: 3
+        }
+    ));
+    assert_eq!(server.requests(), 0);
+
+    let options = PhonemizerOptions {
+        chunking: true,
+        ..Default::default()
+    };
+    let p = limited(&server, options).await;
+    let resp = p.sentence(request("one two three")).await.unwrap();
+    let words: Vec<&str> = resp.words.iter().map(|w| w.phonetic.as_str()).collect();
+    assert_eq!(words, ["one", "two", "three"]);
+    assert_eq!(server.requests(), 2);
+    assert_eq!(resp.words[2].offsets.unwrap().byte_start, 8);
+}
+
+#[tokio::test]
+async fn lexicon_and_word_cache_serve_async_requests() {
+    let server = MockServer::echo();
+    let mut lexicon = Lexicon::new();
+    lexicon.insert("English", "Rustruut", "ɹʌstɹuːt").unwrap();
+    let options = PhonemizerOptions {
+        lexicon: Some(lexicon),
+        word_cache_capacity: 10,
+        ..Default::default()
+    };
+    let p = limited(&server, options).await;
+
+    let resp = p.sentence(request("Rustruut rocks")).await.unwrap();
+    assert_eq!(resp.words[0].phonetic, "ɹʌstɹuːt");
+    assert_eq!(resp.words[1].phonetic, "rocks");
+    let resp = p.sentence(request("rocks Rustruut")).await.unwrap();
+    assert_eq!(resp.words[1].phonetic, "ɹʌstɹuːt");
+
+    // the second sentence is answered without goruut
+    assert_eq!(server.requests(), 1);
+    assert_eq!(p.word_cache_stats().unwrap().hits, 1);
+}
+
+#[tokio::test]
+async fn async_batch_keeps_order_and_item_errors() {
+    let server = MockServer::echo();
+    let p = limited(&server, PhonemizerOptions::default()).await;
+
+    let reqs = vec![request("a b"), request("c d e"), request("f")];
+    let results = p.sentences(reqs).await;
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().words[1].phonetic, "b");
+    assert!(matches!(
+        results[1],
+        Err(RustruutError::WordLimitExceeded { .. })
+    ));
+    assert_eq!(results[2].as_ref().unwrap().words[0].phonetic, "f");
+}
+
+#[derive(Clone)]
+struct TempFolder(String);
+
+impl Folder for TempFolder {
+    fn get_download_dir(&self) -> Option<&str> {
+        Some(&self.0)
+    }
+}
+
+#[tokio::test]
+async fn repeated_async_sentences_are_served_from_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let server = MockServer::echo();
+    let mock = mock_di(&server);
+    let di = DependencyInjection::with_parts(
+        mock.policy,
+        mock.ipa,
+        mock.dict_getter,
+        mock.api,
+        TempFolder(dir.path().to_str().unwrap().to_string()),
+        mock.version,
+    );
+    let options = PhonemizerOptions {
+        disk_cache: Some(DiskCacheOptions::default()),
+        ..Default::default()
+    };
+    let p = AsyncPhonemizer::with_options(di, options).await;
+
+    let first = p.sentence(request("hello world")).await.unwrap();
+    let second = p.sentence(request("hello world")).await.unwrap();
+    assert_eq!(first.words[1].phonetic, second.words[1].phonetic);
+    assert_eq!(server.requests(), 1);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn custom_models_are_loaded_by_the_async_goruut() {
+    use rustruut::test_util::FakeExecutable;
+    use rustruut::usecases::dict::model_path;
+    use serde_json::Value;
+
+    let fake = FakeExecutable::serve_lexicon([("hello", "həloʊ")]);
+    let models = tempfile::tempdir().unwrap();
+    std::fs::write(models.path().join(model_path("English")), b"english").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let di = DependencyInjection::with_parts(
+        di::default_impls::DummyPolicy,
+        di::default_impls::DummyIpaFlavor,
+        di::custom_impls::CustomDict::new(models.path()),
+        di::default_impls::DummyApi,
+        di::custom_impls::CustomExecutable::new(fake.path().to_str().unwrap())
+            .with_download_dir(dir.path().to_str().unwrap()),
+        di::default_impls::DummyVersion,
+    );
+
+    let p = AsyncPhonemizer::new(di).await;
+    let resp = p.sentence(request("hello")).await.unwrap();
+    assert_eq!(resp.words[0].phonetic, "həloʊ");
+
+    // launched as `--configfile <path>`
+    let launches = fake.launches();
+    let config_path = launches[0].split_whitespace().nth(1).unwrap();
+    let config: Value = serde_json::from_slice(&std::fs::read(config_path).unwrap()).unwrap();
+    let file = config["LoadModels"][0]["file"].as_str().unwrap();
+    assert_eq!(config["LoadModels"][0]["lang"], "English");
+    assert_eq!(std::fs::read(file).unwrap(), b"english");
+}
+
+#[cfg(unix)]
+mod spawn {
+    use std::time::Duration;
+
+    use rustruut::test_util::{fast_restart_policy, FakeExecutable};
+    use rustruut::usecases::supervisor_async::AsyncSupervisor;
+
+    #[tokio::test]
+    async fn waits_for_serving_without_blocking() {
+        let fake = FakeExecutable::script("sleep 0.2\necho 'Serving...' >&2\nexec sleep 30");
+        let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+
+        // The runtime keeps making progress while the child starts
+        let ticker = tokio::spawn(async {
+            for _ in 0..5 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        });
+        let mut child = AsyncSupervisor::spawn(exe, &config).await.unwrap();
+        assert!(ticker.is_finished());
+        assert!(child.try_wait().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn fails_when_process_exits() {
+        let fake = FakeExecutable::failing(3);
+        let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+
+        assert!(AsyncSupervisor::spawn(exe, &config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn respawns_killed_child() {
+        let fake = FakeExecutable::idle();
+        let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+
+        let supervisor = AsyncSupervisor::start(exe, &config, fast_restart_policy(3))
+            .await
+            .unwrap();
+        let generation = supervisor.generation().await;
+        let first_pid = supervisor.id().await.unwrap();
+
+        tokio::process::Command::new("kill")
+            .arg("-9")
+            .arg(first_pid.to_string())
+            .status()
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!supervisor.is_running().await);
+
+        assert!(supervisor.recover(generation, false).await.unwrap());
+        assert!(supervisor.is_running().await);
+        assert_ne!(supervisor.id().await, Some(first_pid));
+        assert_eq!(supervisor.restarts().await, 1);
+
+        // A second caller that saw the old generation just retries
+        assert!(supervisor.recover(generation, true).await.unwrap());
+        assert_eq!(supervisor.restarts().await, 1);
+
+        supervisor.mark_healthy().await;
+        assert_eq!(supervisor.restarts().await, 0);
+    }
+
+    #[tokio::test]
+    async fn healthy_child_is_not_restarted_on_application_error() {
+        let fake = FakeExecutable::idle();
+        let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+
+        let supervisor = AsyncSupervisor::start(exe, &config, fast_restart_policy(3))
+            .await
+            .unwrap();
+        let pid = supervisor.id().await;
+
+        let generation = supervisor.generation().await;
+        assert!(!supervisor.recover(generation, false).await.unwrap());
+        assert_eq!(supervisor.id().await, pid);
+    }
+
+    #[tokio::test]
+    async fn restart_budget_is_enforced() {
+        // Reports serving and crashes right away
+        let fake = FakeExecutable::script("echo 'Serving...' >&2\nexit 1");
+        let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+
+        let supervisor = AsyncSupervisor::start(exe, &config, fast_restart_policy(2))
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let generation = supervisor.generation().await;
+            assert!(supervisor.recover(generation, true).await.unwrap());
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let generation = supervisor.generation().await;
+        assert!(supervisor.recover(generation, true).await.is_err());
+    }
+}
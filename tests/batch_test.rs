@@ -0,0 +1,137 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use rustruut::models::requests::PhonemizeSentence;
+use rustruut::models::responses;
+use rustruut::test_util::{mock_phonemizer, request, MockServer};
+use rustruut::usecases::phonemize::PhonemizeUsecase;
+use rustruut::usecases::rustruut::RustruutError;
+use rustruut::PhonemizerOptions;
+use serde_json::json;
+
+#[test]
+fn batch_preserves_order_limits_concurrency_and_reports_item_errors() {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let (f, p) = (in_flight.clone(), peak.clone());
+    let server = MockServer::start(move |body| {
+        let now = f.fetch_add(1, Ordering::SeqCst) + 1;
+        p.fetch_max(now, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(30));
+        f.fetch_sub(1, Ordering::SeqCst);
+
+        let sentence = body["Sentence"].as_str().unwrap_or_default();
+        if sentence == "bad" {
+            // not a response body
+            return json!("garbage");
+        }
+        json!({ "Words": [{ "CleanWord": sentence, "Phonetic": sentence }] })
+    });
+
+    let options = PhonemizerOptions {
+        concurrency: 3,
+        ..Default::default()
+    };
+    let phonemizer = mock_phonemizer(&server, options);
+
+    let sentences: Vec<String> = (0..12)
+        .map(|i| {
+            if i == 5 {
+                "bad".to_string()
+            } else {
+                format!("s{}", i)
+            }
+        })
+        .collect();
+    let results = phonemizer.sentences(sentences.iter().map(|s| request(s)));
+
+    assert_eq!(results.len(), sentences.len());
+    for (i, result) in results.iter().enumerate() {
+        if i == 5 {
+            assert!(result.is_err());
+        } else {
+            let resp = result.as_ref().unwrap();
+            assert_eq!(resp.words[0].clean_word, sentences[i]);
+        }
+    }
+    let peak = peak.load(Ordering::SeqCst);
+    assert!(peak > 1 && peak <= 3, "peak concurrency {}", peak);
+    assert_eq!(server.requests(), sentences.len());
+}
+
+#[test]
+fn empty_batch() {
+    let server = MockServer::echo();
+    let phonemizer = mock_phonemizer(&server, PhonemizerOptions::default());
+
+    assert!(phonemizer.sentences(Vec::new()).is_empty());
+}
+
+#[test]
+fn lazy_input_is_consumed_in_batches() {
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let pulled_at_first_request = Arc::new(AtomicUsize::new(usize::MAX));
+
+    let (p, first) = (pulled.clone(), pulled_at_first_request.clone());
+    let server = MockServer::start(move |body| {
+        let _ = first.compare_exchange(
+            usize::MAX,
+            p.load(Ordering::SeqCst),
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+        let sentence = body["Sentence"].as_str().unwrap_or_default();
+        json!({ "Words": [{ "CleanWord": sentence, "Phonetic": sentence }] })
+    });
+    let phonemizer = mock_phonemizer(&server, PhonemizerOptions::default());
+
+    let total = 2500;
+    let reqs = (0..total).map(|i| {
+        pulled.fetch_add(1, Ordering::SeqCst);
+        request(&format!("s{}", i))
+    });
+    let results = phonemizer.sentences(reqs);
+
+    // requests were sent before the whole input was read
+    assert!(pulled_at_first_request.load(Ordering::SeqCst) < total);
+    assert_eq!(results.len(), total);
+    for (i, result) in results.iter().enumerate() {
+        assert_eq!(
+            result.as_ref().unwrap().words[0].clean_word,
+            format!("s{}", i)
+        );
+    }
+}
+
+struct Upper;
+
+impl PhonemizeUsecase for Upper {
+    fn sentence(
+        &self,
+        req: PhonemizeSentence,
+    ) -> Result<responses::PhonemizeSentence, RustruutError> {
+        if req.sentence.is_empty() {
+            return Err(RustruutError::Generic("empty".to_string()));
+        }
+        Ok(responses::PhonemizeSentence {
+            words: vec![responses::PhonemizeSentenceWord {
+                clean_word: req.sentence.to_uppercase(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+    }
+}
+
+#[test]
+fn default_sentences_calls_sentence_per_request() {
+    let results = Upper.sentences(vec![request("a"), request(""), request("b")]);
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().words[0].clean_word, "A");
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap().words[0].clean_word, "B");
+}
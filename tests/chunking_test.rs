@@ -0,0 +1,194 @@
+use rustruut::interfaces::PolicyMaxWords;
+use rustruut::models::requests::PhonemizeSentence;
+use rustruut::test_util::{mock_di, request, MockServer};
+use rustruut::usecases::chunking::{split, Chunk};
+use rustruut::usecases::rustruut::RustruutError;
+use rustruut::{di, DependencyInjection, Phonemizer, PhonemizerOptions};
+use serde_json::json;
+
+fn texts(chunks: &[Chunk]) -> Vec<&str> {
+    chunks.iter().map(|c| c.text.as_str()).collect()
+}
+
+#[test]
+fn prefers_sentence_then_clause_boundaries() {
+    let chunks = split("One two. Three four five, six seven eight nine ten", 4);
+    assert_eq!(
+        texts(&chunks),
+        vec![
+            "One two.",
+            "Three four five,",
+            "six seven eight nine",
+            "ten"
+        ]
+    );
+    let starts: Vec<bool> = chunks.iter().map(|c| c.starts_sentence).collect();
+    assert_eq!(starts, vec![true, true, false, false]);
+}
+
+#[test]
+fn short_text_is_one_chunk() {
+    let chunks = split("  hello   world ", 5);
+    assert_eq!(texts(&chunks), vec!["hello   world"]);
+    assert_eq!(chunks[0].range, 2..15);
+}
+
+#[test]
+fn chunks_keep_the_original_spacing() {
+    let text = "Één  twee.\nDrie\tvier  vijf,  zes";
+    let chunks = split(text, 3);
+    assert_eq!(
+        texts(&chunks),
+        vec!["Één  twee.", "Drie\tvier  vijf,", "zes"]
+    );
+    for chunk in &chunks {
+        assert_eq!(&text[chunk.range.clone()], chunk.text);
+    }
+}
+
+#[derive(Clone)]
+struct FourWordPolicy;
+impl PolicyMaxWords for FourWordPolicy {
+    fn get_policy_max_words(&self) -> usize {
+        4
+    }
+}
+
+/// Stand-in which enforces the limit like goruut and flags sentence first/last words.
+fn limited_server() -> MockServer {
+    MockServer::start(|body| {
+        let sentence = body["Sentence"].as_str().unwrap_or_default();
+        let words: Vec<&str> = sentence.split_whitespace().collect();
+        if words.len() > 4 {
+            return json!({ "Words": null, "ErrorWordLimitExceeded": true });
+        }
+        let split = body["SplitSentences"].as_bool().unwrap_or(false);
+        let n = words.len();
+        let words: Vec<_> = words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                let prev_end = i > 0 && words[i - 1].ends_with('.');
+                json!({
+                    "CleanWord": w.trim_end_matches(['.', ',']),
+                    "Phonetic": w.to_uppercase(),
+                    "IsFirst": i == 0 || (split && prev_end),
+                    "IsLast": i + 1 == n || (split && w.ends_with('.')),
+                })
+            })
+            .collect();
+        json!({ "Words": words })
+    })
+}
+
+fn phonemizer(
+    server: &MockServer,
+    chunking: bool,
+) -> Phonemizer<
+    FourWordPolicy,
+    di::default_impls::DummyIpaFlavor,
+    di::default_impls::DummyDict,
+    di::custom_impls::CustomApi,
+    di::default_impls::DummyFolder,
+    di::default_impls::DummyVersion,
+> {
+    let options = PhonemizerOptions {
+        chunking,
+        ..Default::default()
+    };
+    phonemizer_with(server, options)
+}
+
+fn phonemizer_with(
+    server: &MockServer,
+    options: PhonemizerOptions,
+) -> Phonemizer<
+    FourWordPolicy,
+    di::default_impls::DummyIpaFlavor,
+    di::default_impls::DummyDict,
+    di::custom_impls::CustomApi,
+    di::default_impls::DummyFolder,
+    di::default_impls::DummyVersion,
+> {
+    let mock = mock_di(server);
+    let di = DependencyInjection::with_parts(
+        FourWordPolicy,
+        mock.ipa,
+        mock.dict_getter,
+        mock.api,
+        mock.folder,
+        mock.version,
+    );
+    Phonemizer::with_options(di, options)
+}
+
+fn split_request(sentence: &str) -> PhonemizeSentence {
+    PhonemizeSentence {
+        split_sentences: true,
+        ..request(sentence)
+    }
+}
+
+const TEXT: &str = "one two. three four five six seven";
+
+#[test]
+fn without_chunking_the_limit_is_hit() {
+    let server = limited_server();
+    let err = phonemizer(&server, false)
+        .sentence(request(TEXT))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RustruutError::WordLimitExceeded {
+            limit: 4,
+            actual: 7
+        }
+    ));
+    assert_eq!(server.requests(), 0);
+}
+
+#[test]
+fn raw_word_limit_returns_goruut_response() {
+    let server = limited_server();
+    let options = PhonemizerOptions {
+        raw_word_limit: true,
+        ..Default::default()
+    };
+    let resp = phonemizer_with(&server, options)
+        .sentence(request(TEXT))
+        .unwrap();
+    assert!(resp.error_word_limit_exceeded);
+    assert!(resp.words.is_empty());
+    assert_eq!(server.requests(), 1);
+}
+
+#[test]
+fn chunks_are_stitched_with_whole_text_flags() {
+    let server = limited_server();
+    let resp = phonemizer(&server, true).sentence(request(TEXT)).unwrap();
+
+    assert!(!resp.error_word_limit_exceeded);
+    let words: Vec<&str> = resp.words.iter().map(|w| w.clean_word.as_str()).collect();
+    assert_eq!(
+        words,
+        vec!["one", "two", "three", "four", "five", "six", "seven"]
+    );
+    let first: Vec<bool> = resp.words.iter().map(|w| w.is_first).collect();
+    let last: Vec<bool> = resp.words.iter().map(|w| w.is_last).collect();
+    assert_eq!(first, vec![true, false, false, false, false, false, false]);
+    assert_eq!(last, vec![false, false, false, false, false, false, true]);
+    assert_eq!(server.requests(), 3);
+}
+
+#[test]
+fn chunks_keep_sentence_flags_when_splitting_sentences() {
+    let server = limited_server();
+    let resp = phonemizer(&server, true)
+        .sentence(split_request(TEXT))
+        .unwrap();
+
+    let first: Vec<bool> = resp.words.iter().map(|w| w.is_first).collect();
+    let last: Vec<bool> = resp.words.iter().map(|w| w.is_last).collect();
+    assert_eq!(first, vec![true, false, true, false, false, false, false]);
+    assert_eq!(last, vec![false, true, false, false, false, false, true]);
+}
@@ -0,0 +1,112 @@
+use rustruut::test_util::MockServer;
+use serde_json::Value;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn rustruut(server: &MockServer, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rustruut"))
+        .args(["--api", &server.url])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn phonemizes_arguments_as_plain_text() {
+    let server = MockServer::echo();
+    let out = rustruut(&server, &["-l", "English", "hello world", "bye"], "");
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "hello world\nbye\n");
+    assert_eq!(server.requests(), 2);
+}
+
+#[test]
+fn reads_stdin_and_files_as_json_lines() {
+    let server = MockServer::echo();
+    let out = rustruut(
+        &server,
+        &["-l", "English", "--output", "jsonl"],
+        "one two\n\nthree\n",
+    );
+    assert!(out.status.success());
+    let lines: Vec<Value> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["Words"][1]["Phonetic"], "two");
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("input.txt");
+    std::fs::write(&path, "a b c\nd\n").unwrap();
+    let out = rustruut(
+        &server,
+        &["-l", "English", "-o", "json", "-f", path.to_str().unwrap()],
+        "",
+    );
+    assert!(out.status.success());
+    let json: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 2);
+    assert_eq!(json[0]["Words"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn options_map_onto_the_request() {
+    let server = MockServer::start(
+        |body| serde_json::json!({ "Words": [{ "CleanWord": "", "Phonetic": body.to_string() }] }),
+    );
+    let out = rustruut(
+        &server,
+        &["-l", "Czech,English", "-r", "-i", "flavor_a", "hɛloʊ"],
+        "",
+    );
+    assert!(out.status.success());
+    let body: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(body["Language"], "Czech");
+    assert_eq!(body["Languages"], serde_json::json!(["Czech", "English"]));
+    assert_eq!(body["IsReverse"], true);
+    assert_eq!(body["IpaFlavors"], serde_json::json!(["flavor_a"]));
+    assert_eq!(body["Sentence"], "hɛloʊ");
+}
+
+#[test]
+fn language_is_required() {
+    let server = MockServer::echo();
+    let out = rustruut(&server, &["hello"], "");
+    assert!(!out.status.success());
+    assert_eq!(server.requests(), 0);
+}
+
+#[test]
+fn streams_stdin_line_by_line() {
+    let server = MockServer::echo();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rustruut"))
+        .args(["--api", &server.url, "-l", "English"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = std::io::BufReader::new(child.stdout.take().unwrap());
+
+    // each answer arrives while stdin is still open
+    for sentence in ["hello world", "bye"] {
+        writeln!(stdin, "{}", sentence).unwrap();
+        stdin.flush().unwrap();
+        let mut line = String::new();
+        std::io::BufRead::read_line(&mut stdout, &mut line).unwrap();
+        assert_eq!(line, format!("{}\n", sentence));
+    }
+    drop(stdin);
+    assert!(child.wait().unwrap().success());
+}
@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+use rustruut::test_util::{mock_phonemizer, request, MockServer};
+use rustruut::PhonemizerOptions;
+
+/// Requests per client in the benchmark.
+const NUM_REQUESTS: usize = 200;
+/// Requests per client in the connection count test, enough to see reuse.
+const NUM_CHECKED: usize = 10;
+
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let idx = ((sorted.len() as f64 - 1.0) * p).round() as usize;
+    sorted[idx]
+}
+
+fn report(name: &str, mut latencies: Vec<Duration>) -> Duration {
+    latencies.sort();
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    println!(
+        "{:<8} p50 {:>7.3}ms  p90 {:>7.3}ms  p99 {:>7.3}ms  max {:>7.3}ms",
+        name,
+        ms(percentile(&latencies, 0.5)),
+        ms(percentile(&latencies, 0.9)),
+        ms(percentile(&latencies, 0.99)),
+        ms(*latencies.last().unwrap()),
+    );
+    percentile(&latencies, 0.5)
+}
+
+/// A fresh client for every request, as the request path used to do.
+fn client_per_request(server: &MockServer, requests: usize) -> Vec<Duration> {
+    let url = format!("{}/tts/phonemize/sentence", server.url);
+    let mut latencies = Vec::with_capacity(requests);
+    for _ in 0..requests {
+        let start = Instant::now();
+        let client = reqwest::blocking::Client::new();
+        let resp = client
+            .post(&url)
+            .json(&request("hello world"))
+            .send()
+            .unwrap();
+        assert!(resp.status().is_success());
+        let _: serde_json::Value = resp.json().unwrap();
+        latencies.push(start.elapsed());
+    }
+    latencies
+}
+
+/// The phonemizer owns one pooled client.
+fn pooled_client(server: &MockServer, requests: usize) -> Vec<Duration> {
+    let phonemizer = mock_phonemizer(server, PhonemizerOptions::default());
+    let mut latencies = Vec::with_capacity(requests);
+    for _ in 0..requests {
+        let start = Instant::now();
+        let resp = phonemizer.sentence(request("hello world")).unwrap();
+        assert_eq!(resp.words.len(), 2);
+        latencies.push(start.elapsed());
+    }
+    latencies
+}
+
+#[test]
+fn pooled_client_reuses_one_connection() {
+    let server = MockServer::echo();
+    client_per_request(&server, NUM_CHECKED);
+    let connections_before = server.connections();
+    pooled_client(&server, NUM_CHECKED);
+    let connections_after = server.connections() - connections_before;
+
+    assert_eq!(connections_before, NUM_CHECKED);
+    assert_eq!(connections_after, 1);
+}
+
+// timings depend on the machine, run with `cargo test --test client_bench -- --ignored`
+#[test]
+#[ignore = "benchmark, compares wall-clock latencies"]
+fn bench_pooled_client_against_client_per_request() {
+    let server = MockServer::echo();
+    let before = client_per_request(&server, NUM_REQUESTS);
+    let after = pooled_client(&server, NUM_REQUESTS);
+
+    println!("\n=== Client Latency ({} requests) ===", NUM_REQUESTS);
+    let p50_before = report("before", before);
+    let p50_after = report("after", after);
+    println!("====================================\n");
+
+    assert!(p50_after < p50_before);
+}
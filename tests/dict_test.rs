@@ -0,0 +1,118 @@
+use rustruut::interfaces::DictGetter;
+use rustruut::test_util::{mock_di, request, MockServer};
+use rustruut::usecases::config::Config;
+use rustruut::usecases::dict::{dictionary_path, materialize, model_path};
+use rustruut::{di, DependencyInjection, Lexicon, Phonemizer, PhonemizerOptions};
+use serde_json::Value;
+
+#[test]
+fn directory_dict_serves_relative_paths_only() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("English.zlib"), b"weights").unwrap();
+    let dict = di::custom_impls::CustomDict::new(dir.path());
+
+    assert_eq!(dict.get("English.zlib"), Some(b"weights".to_vec()));
+    assert_eq!(dict.get("Czech.zlib"), None);
+    assert_eq!(dict.get("../English.zlib"), None);
+    assert_eq!(dict.get("/etc/passwd"), None);
+}
+
+#[test]
+fn models_are_materialized_and_listed_in_the_config() {
+    let source = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join(model_path("English")), b"english").unwrap();
+    std::fs::write(source.path().join(model_path("Hebrew3")), b"hebrew").unwrap();
+    let dict = di::custom_impls::CustomDict::new(source.path());
+
+    let target = tempfile::tempdir().unwrap();
+    let models = materialize(&dict, target.path()).unwrap();
+    assert_eq!(models.len(), 2);
+    assert_eq!(std::fs::read(&models["English"]).unwrap(), b"english");
+    assert!(models["Hebrew3"].starts_with(target.path().to_str().unwrap()));
+
+    // Unchanged models resolve to the same file
+    assert_eq!(materialize(&dict, target.path()).unwrap(), models);
+
+    let di = DependencyInjection::with_parts(
+        di::default_impls::DummyPolicy,
+        di::default_impls::DummyIpaFlavor,
+        dict,
+        di::default_impls::DummyApi,
+        di::default_impls::DummyFolder,
+        di::default_impls::DummyVersion,
+    );
+    let config_path = target.path().join("config.json");
+    Config::new(di)
+        .serialize(config_path.to_str().unwrap(), &models)
+        .unwrap();
+    let config: Value = serde_json::from_slice(&std::fs::read(&config_path).unwrap()).unwrap();
+    let loaded = config["LoadModels"].as_array().unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0]["lang"], "English");
+    assert_eq!(loaded[0]["file"], models["English"].as_str());
+    assert_eq!(loaded[1]["lang"], "Hebrew3");
+}
+
+fn with_dict(
+    server: &MockServer,
+    dir: &std::path::Path,
+    options: PhonemizerOptions,
+) -> Phonemizer<
+    di::default_impls::DummyPolicy,
+    di::default_impls::DummyIpaFlavor,
+    di::custom_impls::CustomDict,
+    di::custom_impls::CustomApi,
+    di::default_impls::DummyFolder,
+    di::default_impls::DummyVersion,
+> {
+    let mock = mock_di(server);
+    let di = DependencyInjection::with_parts(
+        mock.policy,
+        mock.ipa,
+        di::custom_impls::CustomDict::new(dir),
+        mock.api,
+        mock.folder,
+        mock.version,
+    );
+    Phonemizer::with_options(di, options)
+}
+
+#[test]
+fn dictionaries_override_goruut_below_the_user_lexicon() {
+    let source = tempfile::tempdir().unwrap();
+    std::fs::write(
+        source.path().join(dictionary_path("English")),
+        "rustruut\tɹʌstɹuːt\ngoruut\tɡoɹuːt\n",
+    )
+    .unwrap();
+    let server = MockServer::echo();
+
+    let p = with_dict(&server, source.path(), PhonemizerOptions::default());
+    let resp = p.sentence(request("rustruut and goruut")).unwrap();
+    assert_eq!(resp.words[0].phonetic, "ɹʌstɹuːt");
+    assert_eq!(resp.words[1].phonetic, "and");
+    assert_eq!(resp.words[2].phonetic, "ɡoɹuːt");
+
+    let mut lexicon = Lexicon::new();
+    lexicon.insert("English", "goruut", "ɡəɹuːt").unwrap();
+    let options = PhonemizerOptions {
+        lexicon: Some(lexicon),
+        ..Default::default()
+    };
+    let p = with_dict(&server, source.path(), options);
+    let resp = p.sentence(request("rustruut and goruut")).unwrap();
+    assert_eq!(resp.words[0].phonetic, "ɹʌstɹuːt");
+    assert_eq!(resp.words[2].phonetic, "ɡəɹuːt");
+}
+
+#[test]
+fn broken_dictionaries_are_reported() {
+    let source = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join(dictionary_path("Czech")), "one field\n").unwrap();
+    let server = MockServer::echo();
+
+    let p = with_dict(&server, source.path(), PhonemizerOptions::default());
+    let err = p.sentence(request("hello")).unwrap_err();
+    assert!(err.to_string().contains("Line 1"), "{}", err);
+    assert_eq!(server.requests(), 0);
+}
@@ -0,0 +1,172 @@
+use rustruut::interfaces::Folder;
+use rustruut::models::requests::PhonemizeSentence;
+use rustruut::models::responses::{PhonemizeSentence as Resp, PhonemizeSentenceWord};
+use rustruut::test_util::{mock_di, request, MockServer};
+use rustruut::usecases::cache::DiskCache;
+use rustruut::{di, DependencyInjection, DiskCacheOptions, Phonemizer, PhonemizerOptions};
+
+#[derive(Clone)]
+struct TempFolder(String);
+
+impl Folder for TempFolder {
+    fn get_download_dir(&self) -> Option<&str> {
+        Some(&self.0)
+    }
+}
+
+fn response(word: &str) -> Resp {
+    Resp {
+        words: vec![PhonemizeSentenceWord {
+            clean_word: word.to_string(),
+            phonetic: word.to_string(),
+            ..Default::default()
+        }],
+        error_word_limit_exceeded: false,
+    }
+}
+
+fn phonemizer(
+    server: &MockServer,
+    folder: &str,
+) -> Phonemizer<
+    di::default_impls::DummyPolicy,
+    di::default_impls::DummyIpaFlavor,
+    di::default_impls::DummyDict,
+    di::custom_impls::CustomApi,
+    TempFolder,
+    di::default_impls::DummyVersion,
+> {
+    let mock = mock_di(server);
+    let di = DependencyInjection::with_parts(
+        mock.policy,
+        mock.ipa,
+        mock.dict_getter,
+        mock.api,
+        TempFolder(folder.to_string()),
+        mock.version,
+    );
+    let options = PhonemizerOptions {
+        disk_cache: Some(DiskCacheOptions::default()),
+        ..Default::default()
+    };
+    Phonemizer::with_options(di, options)
+}
+
+#[test]
+fn repeated_sentences_are_served_from_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let folder = dir.path().to_str().unwrap();
+    let server = MockServer::echo();
+
+    let p = phonemizer(&server, folder);
+    let first = p.sentence(request("hello world")).unwrap();
+    let second = p.sentence(request("hello world")).unwrap();
+    assert_eq!(first.words.len(), second.words.len());
+    assert_eq!(server.requests(), 1);
+
+    // A different reverse flag is a different key
+    p.sentence(PhonemizeSentence {
+        is_reverse: true,
+        ..request("hello world")
+    })
+    .unwrap();
+    assert_eq!(server.requests(), 2);
+
+    // A new phonemizer on the same folder reuses the cache
+    drop(p);
+    let p = phonemizer(&server, folder);
+    p.sentence(request("hello world")).unwrap();
+    assert_eq!(server.requests(), 2);
+}
+
+#[test]
+fn other_engine_version_invalidates_the_cache() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let cache = DiskCache::open(dir.path(), "v0.7.0", DiskCacheOptions::default()).unwrap();
+    cache.put(&request("a"), &response("a")).unwrap();
+    assert!(cache.get(&request("a")).is_some());
+    let old_dir = cache.dir().to_path_buf();
+
+    let cache = DiskCache::open(dir.path(), "v0.8.0", DiskCacheOptions::default()).unwrap();
+    assert!(cache.get(&request("a")).is_none());
+
+    // a process still running the old release keeps its entries
+    let old = DiskCache::open(dir.path(), "v0.7.0", DiskCacheOptions::default()).unwrap();
+    assert_eq!(old.dir(), old_dir);
+    assert!(old.get(&request("a")).is_some());
+}
+
+#[cfg(unix)]
+#[test]
+fn unused_engine_versions_are_removed() {
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempfile::tempdir().unwrap();
+    let cache = DiskCache::open(dir.path(), "v0.7.0", DiskCacheOptions::default()).unwrap();
+    cache.put(&request("a"), &response("a")).unwrap();
+    let old_dir = cache.dir().to_path_buf();
+
+    // last used two months ago
+    let then = SystemTime::now() - Duration::from_secs(60 * 24 * 60 * 60);
+    for entry in std::fs::read_dir(&old_dir).unwrap() {
+        let file = File::options()
+            .write(true)
+            .open(entry.unwrap().path())
+            .unwrap();
+        file.set_modified(then).unwrap();
+    }
+    File::open(&old_dir).unwrap().set_modified(then).unwrap();
+
+    DiskCache::open(dir.path(), "v0.8.0", DiskCacheOptions::default()).unwrap();
+    assert!(!old_dir.exists());
+}
+
+#[test]
+fn replacing_an_entry_counts_only_the_new_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = DiskCache::open(dir.path(), "v0.8.0", DiskCacheOptions::default()).unwrap();
+
+    cache.put(&request("a"), &response("short")).unwrap();
+    let short = cache.bytes();
+    cache
+        .put(&request("a"), &response("a much longer word"))
+        .unwrap();
+    let long = cache.bytes();
+    assert!(long > short);
+    cache
+        .put(&request("a"), &response("a much longer word"))
+        .unwrap();
+
+    assert_eq!(cache.bytes(), long);
+    assert_eq!(cache.entries(), 1);
+    let on_disk: u64 = std::fs::read_dir(cache.dir())
+        .unwrap()
+        .map(|e| e.unwrap().metadata().unwrap().len())
+        .sum();
+    assert_eq!(cache.bytes(), on_disk);
+}
+
+#[test]
+fn least_recently_used_entries_are_evicted() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = DiskCacheOptions {
+        max_bytes: u64::MAX,
+        max_entries: 3,
+    };
+    let cache = DiskCache::open(dir.path(), "v0.8.0", options).unwrap();
+
+    for word in ["a", "b", "c"] {
+        cache.put(&request(word), &response(word)).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    // "a" becomes the most recently used
+    assert!(cache.get(&request("a")).is_some());
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    cache.put(&request("d"), &response("d")).unwrap();
+
+    assert!(cache.get(&request("a")).is_some());
+    assert!(cache.get(&request("b")).is_none());
+    assert!(cache.get(&request("d")).is_some());
+}
@@ -0,0 +1,67 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use rustruut::test_util::{executable_content, executable_for, Faults, FileServer};
+use rustruut::usecases::executable::Executable;
+
+const PROCESSES: usize = 8;
+const SIZE: usize = 1_000_000;
+
+// set for the copies of this test binary started by the parent test
+const DIR_ENV: &str = "RUSTRUUT_LOCK_TEST_DIR";
+const URL_ENV: &str = "RUSTRUUT_LOCK_TEST_URL";
+
+fn executable(url: &str) -> Executable {
+    executable_for(&executable_content(SIZE), vec![format!("{}/v0.8.0/", url)])
+}
+
+/// Body of the child processes, does nothing when run as a normal test.
+#[test]
+fn child_process() {
+    let (Ok(dir), Ok(url)) = (std::env::var(DIR_ENV), std::env::var(URL_ENV)) else {
+        return;
+    };
+    let path = executable(&url)
+        .exists_or_download(Path::new(&dir))
+        .unwrap();
+    assert_eq!(std::fs::read(path).unwrap(), executable_content(SIZE));
+}
+
+#[test]
+fn concurrent_processes_download_once() {
+    // dropped connections keep the download going long enough to overlap
+    let server = FileServer::with_faults(
+        executable_content(SIZE),
+        Faults {
+            drop_after: Some(SIZE / 10),
+            ..Default::default()
+        },
+    );
+    let dir = tempfile::tempdir().unwrap();
+
+    let children: Vec<_> = (0..PROCESSES)
+        .map(|_| {
+            Command::new(std::env::current_exe().unwrap())
+                .args(["child_process", "--exact", "--test-threads=1"])
+                .env(DIR_ENV, dir.path())
+                .env(URL_ENV, &server.url)
+                .stdout(Stdio::null())
+                .spawn()
+                .unwrap()
+        })
+        .collect();
+    for mut child in children {
+        assert!(child.wait().unwrap().success());
+    }
+
+    // one process downloaded in ten requests, the others found the executable
+    assert_eq!(server.ranges().len(), 10);
+    assert_eq!(server.ranges()[0], None);
+    let executable = executable(&server.url);
+    let path = dir.path().join(executable.file_name());
+    assert_eq!(std::fs::read(path).unwrap(), executable_content(SIZE));
+    assert!(!dir
+        .path()
+        .join(format!("{}.part", executable.file_name()))
+        .exists());
+}
@@ -0,0 +1,108 @@
+use std::path::Path;
+
+use rustruut::test_util::{executable_content, executable_for, Faults, FileServer};
+use rustruut::usecases::executable::{Executable, ExecutableError};
+
+const SIZE: usize = 200_000;
+
+fn executable(server: &FileServer) -> Executable {
+    executable_for(
+        &executable_content(SIZE),
+        vec![format!("{}/v0.8.0/", server.url)],
+    )
+}
+
+fn part_path(executable: &Executable, dir: &Path) -> std::path::PathBuf {
+    dir.join(format!("{}.part", executable.file_name()))
+}
+
+#[test]
+fn resumes_after_dropped_connections() {
+    let server = FileServer::with_faults(
+        executable_content(SIZE),
+        Faults {
+            drop_after: Some(64_000),
+            ..Default::default()
+        },
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let executable = executable(&server);
+
+    let path = executable.download(dir.path()).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), executable_content(SIZE));
+    assert!(!part_path(&executable, dir.path()).exists());
+    assert_eq!(
+        server.ranges(),
+        vec![None, Some(64_000), Some(128_000), Some(192_000)]
+    );
+}
+
+#[test]
+fn continues_part_left_by_earlier_download() {
+    let server = FileServer::start(executable_content(SIZE));
+    let dir = tempfile::tempdir().unwrap();
+    let executable = executable(&server);
+    std::fs::write(
+        part_path(&executable, dir.path()),
+        &executable_content(SIZE)[..SIZE / 2],
+    )
+    .unwrap();
+
+    let path = executable.download(dir.path()).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), executable_content(SIZE));
+    assert_eq!(server.ranges(), vec![Some(SIZE as u64 / 2)]);
+}
+
+#[test]
+fn starts_over_when_server_ignores_range() {
+    let server = FileServer::with_faults(
+        executable_content(SIZE),
+        Faults {
+            ignore_range: true,
+            ..Default::default()
+        },
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let executable = executable(&server);
+    // not a prefix of the file, only a full download can fix it
+    std::fs::write(part_path(&executable, dir.path()), vec![0; 1000]).unwrap();
+
+    let path = executable.download(dir.path()).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), executable_content(SIZE));
+    assert_eq!(server.ranges(), vec![Some(1000)]);
+}
+
+#[test]
+fn corrupted_download_is_rejected() {
+    let mut served = executable_content(SIZE);
+    served[SIZE / 2] ^= 1;
+    let server = FileServer::start(served);
+    let dir = tempfile::tempdir().unwrap();
+    let executable = executable(&server);
+
+    match executable.download(dir.path()).unwrap_err() {
+        ExecutableError::Download(msg) => assert_eq!(msg, "SHA256 mismatch"),
+        err => panic!("{}", err),
+    }
+    assert!(!dir.path().join(executable.file_name()).exists());
+    let part = std::fs::metadata(part_path(&executable, dir.path())).unwrap();
+    assert_eq!(part.len(), 0);
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn async_resumes_after_dropped_connections() {
+    let server = FileServer::with_faults(
+        executable_content(SIZE),
+        Faults {
+            drop_after: Some(64_000),
+            ..Default::default()
+        },
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let executable = executable(&server);
+
+    let path = executable.download_async(dir.path()).await.unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), executable_content(SIZE));
+    assert_eq!(server.ranges().len(), 4);
+}
@@ -0,0 +1,94 @@
+use rustruut::test_util::{mock_phonemizer, MockServer};
+use rustruut::usecases::rustruut::RustruutError;
+use rustruut::{DictFormat, DictWriter, PhonemizerOptions};
+use serde_json::json;
+
+fn write(format: DictFormat, entries: &[(&str, &str)]) -> String {
+    let mut writer = DictWriter::new(Vec::new(), format);
+    for (word, phonetic) in entries {
+        writer.write(word, phonetic).unwrap();
+    }
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+#[test]
+fn phonetic_is_split_into_phonemes() {
+    let out = write(DictFormat::Kaldi, &[("church", "ˈt͡ʃɜːt͡ʃ"), ("pin", "pʰɪ̃n")]);
+    assert_eq!(out, "church t͡ʃ ɜː t͡ʃ\npin pʰ ɪ̃ n\n");
+}
+
+#[test]
+fn formats_follow_their_layouts() {
+    let entries = [("read", "ɹiːd"), ("read", "ɹɛd")];
+    assert_eq!(
+        write(DictFormat::Mfa, &entries),
+        "read\tɹ iː d\nread\tɹ ɛ d\n"
+    );
+    assert_eq!(
+        write(DictFormat::CmuDict, &[("read", "ˈɹiːd"), ("read", "ˈɹɛd")]),
+        "READ  R IY1 D\nREAD(1)  R EH1 D\n"
+    );
+    assert_eq!(
+        write(DictFormat::Tsv, &[("hello", "həˈloʊ")]),
+        "hello\thəˈloʊ\n"
+    );
+}
+
+#[test]
+fn word_lists_are_exported_through_the_phonemizer() {
+    let server = MockServer::start(|body| {
+        let phonetic = match body["Sentence"].as_str().unwrap_or_default() {
+            "cat" => "kæt",
+            "dog" => "dɒɡ",
+            _ => return json!({ "Words": [] }),
+        };
+        json!({ "Words": [{ "CleanWord": body["Sentence"], "Phonetic": phonetic }] })
+    });
+    let p = mock_phonemizer(&server, PhonemizerOptions::default());
+
+    let mut out = Vec::new();
+    let report = p
+        .export_dictionary(
+            "English",
+            ["cat", " dog", "cat", "", "xyz"],
+            DictFormat::Mfa,
+            &mut out,
+        )
+        .unwrap();
+
+    assert_eq!(String::from_utf8(out).unwrap(), "cat\tk æ t\ndog\td ɒ ɡ\n");
+    assert_eq!(report.written, 2);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "xyz");
+    assert_eq!(server.requests(), 3);
+}
+
+#[test]
+fn cmudict_rejects_sounds_without_arpabet_symbol() {
+    let mut writer = DictWriter::new(Vec::new(), DictFormat::CmuDict);
+    let err = writer.write("hello", "ǀʰəˈloʊ").unwrap_err();
+    assert!(matches!(err, RustruutError::Conversion(_)), "{}", err);
+
+    // the failed pronunciation does not use up a variant number
+    writer.write("hello", "həˈloʊ").unwrap();
+    writer.write("hello", "hɛˈloʊ").unwrap();
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        "HELLO  HH AH0 L OW1\nHELLO(1)  HH EH0 L OW1\n"
+    );
+}
+
+#[test]
+fn cmudict_export_is_english_only() {
+    assert!(DictFormat::CmuDict.supports("en"));
+    assert!(DictFormat::CmuDict.supports("EnglishBritish"));
+    assert!(DictFormat::Mfa.supports("Czech"));
+
+    let server = MockServer::echo();
+    let p = mock_phonemizer(&server, PhonemizerOptions::default());
+    let err = p
+        .export_dictionary("Czech", ["ahoj"], DictFormat::CmuDict, Vec::new())
+        .unwrap_err();
+    assert!(err.to_string().contains("only English"), "{}", err);
+    assert_eq!(server.requests(), 0);
+}
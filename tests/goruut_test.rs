@@ -0,0 +1,77 @@
+#![cfg(unix)]
+
+use std::collections::HashMap;
+
+use rustruut::test_util::{mock_di, request, FakeExecutable};
+use rustruut::usecases::rustruut::{Goruut, RustruutError};
+use rustruut::{di, DependencyInjection, PhonemizerOptions};
+
+fn di() -> DependencyInjection<
+    di::default_impls::DummyPolicy,
+    di::default_impls::DummyIpaFlavor,
+    di::default_impls::DummyDict,
+    di::default_impls::DummyApi,
+    di::default_impls::DummyFolder,
+    di::default_impls::DummyVersion,
+> {
+    DependencyInjection::with_parts(
+        di::default_impls::DummyPolicy,
+        di::default_impls::DummyIpaFlavor,
+        di::default_impls::DummyDict,
+        di::default_impls::DummyApi,
+        di::default_impls::DummyFolder,
+        di::default_impls::DummyVersion,
+    )
+}
+
+#[test]
+fn spawns_executable_and_phonemizes_through_it() {
+    let fake = FakeExecutable::serve_lexicon([("hello", "həloʊ")]);
+    let dir = tempfile::tempdir().unwrap();
+    let goruut = Goruut::with_executable(
+        di(),
+        fake.path(),
+        dir.path().to_str(),
+        HashMap::new(),
+        &PhonemizerOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(goruut.version(), None);
+
+    let resp = goruut.phonemize(request("hello there")).unwrap();
+    assert_eq!(resp.words[0].phonetic, "həloʊ");
+    assert_eq!(resp.words[1].phonetic, "there");
+
+    // started like goruut, with the config written to the writeable folder
+    let launches = fake.launches();
+    assert_eq!(launches.len(), 1);
+    let config = launches[0].strip_prefix("--configfile ").unwrap();
+    assert!(config.starts_with(dir.path().to_str().unwrap()));
+    assert!(std::path::Path::new(config).exists());
+}
+
+#[test]
+fn executable_exiting_before_serving_is_an_error() {
+    let fake = FakeExecutable::failing(3);
+    let dir = tempfile::tempdir().unwrap();
+    let err = Goruut::with_executable(
+        di(),
+        fake.path(),
+        dir.path().to_str(),
+        HashMap::new(),
+        &PhonemizerOptions::default(),
+    )
+    .err()
+    .unwrap();
+    assert!(matches!(err, RustruutError::Process(_)), "{}", err);
+    assert_eq!(fake.launches().len(), 1);
+}
+
+#[test]
+fn new_with_the_original_signature() {
+    let server = rustruut::test_util::MockServer::echo();
+    let di = mock_di(&server);
+    let goruut = Goruut::new(di, None, None, None, HashMap::new()).unwrap();
+    let resp = goruut.phonemize(request("hello")).unwrap();
+    assert_eq!(resp.words[0].phonetic, "hello");
+}
@@ -0,0 +1,112 @@
+use rustruut::interfaces::IpaFlavor;
+use rustruut::models::requests::PhonemizeSentence;
+use rustruut::test_util::{mock_di, request, MockServer};
+use rustruut::usecases::ipa_flavor::Substitution;
+use rustruut::{di, DependencyInjection, Phonemizer};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn longest_match_wins_and_replacements_do_not_chain() {
+    let sub = Substitution::new(&map(&[("t", "T"), ("tʃ", "č"), ("a", "b"), ("b", "a")]));
+    assert_eq!(sub.apply("tʃat"), "čbT");
+    assert_eq!(sub.apply("ab"), "ba");
+    assert_eq!(sub.inverse().apply("čbT"), "tʃat");
+}
+
+#[derive(Clone)]
+struct Broad;
+
+impl IpaFlavor for Broad {
+    fn get_ipa_flavors(&self) -> HashMap<String, HashMap<String, String>> {
+        HashMap::from([("broad".to_string(), map(&[("ɹ", "r"), ("oʊ", "o")]))])
+    }
+}
+
+/// Stand-in answering "ɹoʊd" for every word, recording the request bodies.
+fn server() -> (MockServer, Arc<Mutex<Vec<Value>>>) {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    let server = MockServer::start(move |body| {
+        log.lock().unwrap().push(body.clone());
+        let words: Vec<_> = body["Sentence"]
+            .as_str()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|w| json!({ "CleanWord": w, "Phonetic": "ɹoʊd" }))
+            .collect();
+        json!({ "Words": words })
+    });
+    (server, seen)
+}
+
+fn phonemizer(
+    server: &MockServer,
+) -> Phonemizer<
+    di::default_impls::DummyPolicy,
+    Broad,
+    di::default_impls::DummyDict,
+    di::custom_impls::CustomApi,
+    di::default_impls::DummyFolder,
+    di::default_impls::DummyVersion,
+> {
+    let mock = mock_di(server);
+    let di = DependencyInjection::with_parts(
+        mock.policy,
+        Broad,
+        mock.dict_getter,
+        mock.api,
+        mock.folder,
+        mock.version,
+    );
+    Phonemizer::new(di)
+}
+
+fn flavored_request(sentence: &str, flavors: &[&str], is_reverse: bool) -> PhonemizeSentence {
+    PhonemizeSentence {
+        ipa_flavors: flavors.iter().map(|f| f.to_string()).collect(),
+        is_reverse,
+        ..request(sentence)
+    }
+}
+
+#[test]
+fn custom_flavor_changes_the_output() {
+    let (server, seen) = server();
+    let p = phonemizer(&server);
+
+    let plain = p.sentence(flavored_request("road", &[], false)).unwrap();
+    assert_eq!(plain.words[0].phonetic, "ɹoʊd");
+
+    let broad = p
+        .sentence(flavored_request("road", &["broad"], false))
+        .unwrap();
+    assert_eq!(broad.words[0].phonetic, "rod");
+
+    // goruut only sees the flavors it has to handle itself
+    let broad = p
+        .sentence(flavored_request("road", &["broad", "goruut_flavor"], false))
+        .unwrap();
+    assert_eq!(broad.words[0].phonetic, "rod");
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen[1]["IpaFlavors"], json!([]));
+    assert_eq!(seen[2]["IpaFlavors"], json!(["goruut_flavor"]));
+}
+
+#[test]
+fn reverse_requests_undo_the_flavor_first() {
+    let (server, seen) = server();
+    let p = phonemizer(&server);
+
+    p.sentence(flavored_request("rod", &["broad"], true))
+        .unwrap();
+    assert_eq!(seen.lock().unwrap()[0]["Sentence"], "ɹoʊd");
+}
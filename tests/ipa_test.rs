@@ -0,0 +1,79 @@
+use rustruut::ipa::{tokenize, Length, Phoneme, Stress};
+use rustruut::PhonemizeSentenceWord;
+
+fn symbols(ipa: &str) -> Vec<String> {
+    tokenize(ipa).iter().map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn splits_plain_symbols() {
+    assert_eq!(symbols("kæt"), vec!["k", "æ", "t"]);
+    assert_eq!(
+        symbols("hɛloʊ wɜːld"),
+        vec!["h", "ɛ", "l", "o", "ʊ", "w", "ɜː", "l", "d"]
+    );
+}
+
+#[test]
+fn diacritics_ties_and_length_stay_with_their_symbol() {
+    let phonemes = tokenize("t\u{361}ʃʰi\u{303}ːːd\u{325}ˑe\u{31E}e\u{306}");
+    assert_eq!(
+        phonemes[0],
+        Phoneme {
+            base: "t͡ʃ".to_string(),
+            diacritics: "ʰ".to_string(),
+            ..Default::default()
+        }
+    );
+    assert_eq!(phonemes[1].base, "i");
+    assert_eq!(phonemes[1].diacritics, "\u{0303}");
+    assert_eq!(phonemes[1].length, Length::ExtraLong);
+    assert_eq!(phonemes[2].length, Length::HalfLong);
+    assert_eq!(phonemes[3].diacritics, "\u{031E}");
+    assert_eq!(phonemes[4].length, Length::ExtraShort);
+    assert_eq!(phonemes.len(), 5);
+    assert_eq!(phonemes[0].symbol(), "t͡ʃʰ");
+    assert_eq!(phonemes[1].symbol(), "i\u{303}ːː");
+}
+
+#[test]
+fn stress_marks_the_next_phoneme() {
+    let phonemes = tokenize("ˌɪntəˈnæʃ.ən.əl");
+    let stressed: Vec<(String, Stress)> = phonemes
+        .iter()
+        .filter(|p| p.stress != Stress::None)
+        .map(|p| (p.base.clone(), p.stress))
+        .collect();
+    assert_eq!(
+        stressed,
+        vec![
+            ("ɪ".to_string(), Stress::Secondary),
+            ("n".to_string(), Stress::Primary)
+        ]
+    );
+    assert_eq!(symbols("ˌɪntəˈnæʃ.ən.əl").concat(), "ˌɪntəˈnæʃənəl");
+}
+
+#[test]
+fn word_accessor_tokenizes_phonetic() {
+    let word = PhonemizeSentenceWord {
+        phonetic: "ˈdʒʌmp".to_string(),
+        ..Default::default()
+    };
+    let phonemes: Vec<String> = word.phonemes().iter().map(|p| p.symbol()).collect();
+    assert_eq!(phonemes, vec!["d", "ʒ", "ʌ", "m", "p"]);
+    assert_eq!(word.phonemes()[0].stress, Stress::Primary);
+}
+
+#[test]
+fn vowels_and_syllabic_consonants() {
+    let phonemes = tokenize("ɑu\u{32F}l\u{329}t");
+    let syllabic: Vec<(bool, bool)> = phonemes
+        .iter()
+        .map(|p| (p.is_vowel(), p.is_syllabic()))
+        .collect();
+    assert_eq!(
+        syllabic,
+        vec![(true, true), (true, false), (false, true), (false, false)]
+    );
+}
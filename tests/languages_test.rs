@@ -0,0 +1,103 @@
+use rustruut::models::requests::PhonemizeSentence;
+use rustruut::test_util::{mock_phonemizer, MockServer};
+use rustruut::usecases::rustruut::RustruutError;
+use rustruut::{Language, Languages, PhonemizerOptions};
+
+fn request(language: &str, languages: &[&str]) -> PhonemizeSentence {
+    PhonemizeSentence {
+        ipa_flavors: vec![],
+        language: language.to_string(),
+        languages: languages.iter().map(|l| l.to_string()).collect(),
+        sentence: "hello".to_string(),
+        is_reverse: false,
+        split_sentences: false,
+    }
+}
+
+#[test]
+fn accepts_codes_names_and_dialects_in_any_case() {
+    let en = Language::parse("en").unwrap();
+    assert_eq!(en.name(), "English");
+    assert_eq!(en.code(), Some("en"));
+    assert_eq!(Language::parse("ENGLISH").unwrap(), en);
+    assert_eq!(
+        "english british".parse::<Language>().unwrap().name(),
+        "EnglishBritish"
+    );
+    let hebrew = Language::parse("hebrew3").unwrap();
+    assert_eq!(hebrew.name(), "Hebrew3");
+    assert_eq!(hebrew.code(), None);
+}
+
+#[test]
+fn typos_get_suggestions() {
+    let err = Language::parse("Englsh").unwrap_err();
+    assert_eq!(err.suggestions.first().map(String::as_str), Some("English"));
+    assert!(err.to_string().contains("did you mean"));
+
+    let err = Language::parse("Klingon").unwrap_err();
+    assert!(err.suggestions.is_empty());
+    assert_eq!(err.to_string(), "unknown language \"Klingon\"");
+}
+
+#[test]
+fn init_validates_and_canonicalizes() {
+    let mut req = request("", &["cs", "en"]);
+    req.init().unwrap();
+    assert_eq!(req.language, "Czech");
+    assert_eq!(req.languages, vec!["Czech", "English"]);
+
+    let mut req = request("", &[]);
+    req.init().unwrap();
+    assert_eq!(req.language, "");
+
+    assert!(request("Englsh", &[]).init().is_err());
+    assert!(request("English", &["Czeck"]).init().is_err());
+}
+
+#[test]
+fn invalid_languages_never_reach_goruut() {
+    let server = MockServer::echo();
+    let p = mock_phonemizer(&server, PhonemizerOptions::default());
+    let err = p.sentence(request("Englsh", &[])).unwrap_err();
+    assert!(matches!(err, RustruutError::Language(_)));
+    assert_eq!(server.requests(), 0);
+}
+
+#[test]
+fn lists_languages_sorted() {
+    let languages = Languages::new().languages();
+    assert!(languages.iter().any(|l| l.name() == "Uyghur"));
+    assert!(languages.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn lists_languages_per_release() {
+    let languages = Languages::for_release("v0.7.0").unwrap().languages();
+    assert!(languages.iter().any(|l| l.name() == "Uyghur"));
+    assert!(Languages::for_release("v0.8").is_some());
+    assert!(Languages::for_release("v9.9.9").is_none());
+
+    // every release has a language table
+    for release in rustruut::usecases::release::get_releases() {
+        let languages = Languages::for_release(&release.version).unwrap();
+        assert!(languages.resolve("en").is_ok());
+    }
+}
+
+#[test]
+fn unknown_languages_can_be_passed_through() {
+    let mut req = request("Klingon", &["en", "Klingon"]);
+    req.init_lenient();
+    assert_eq!(req.language, "Klingon");
+    assert_eq!(req.languages, vec!["English", "Klingon"]);
+
+    let server = MockServer::echo();
+    let options = PhonemizerOptions {
+        allow_unknown_languages: true,
+        ..Default::default()
+    };
+    let p = mock_phonemizer(&server, options);
+    p.sentence(request("Klingon", &[])).unwrap();
+    assert_eq!(server.requests(), 1);
+}
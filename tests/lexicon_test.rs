@@ -0,0 +1,192 @@
+use rustruut::models::requests::PhonemizeSentence;
+use rustruut::test_util::{mock_phonemizer, request, MockPhonemizer, MockServer};
+use rustruut::{Lexicon, LexiconError, PhonemizerOptions};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+/// Stand-in phonemizing every word to upper case and tagging "read" as VERB,
+/// every other word as NOUN. Trailing commas are split off like goruut does.
+/// Records the sentences it was asked for.
+fn server() -> (MockServer, Arc<Mutex<Vec<String>>>) {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    let server = MockServer::start(move |body: &Value| {
+        let sentence = body["Sentence"].as_str().unwrap_or_default();
+        log.lock().unwrap().push(sentence.to_string());
+        let words: Vec<_> = sentence
+            .split_whitespace()
+            .map(|token| {
+                let w = token.trim_end_matches(',');
+                let tag = if w == "read" { "VERB" } else { "NOUN" };
+                json!({
+                    "CleanWord": w,
+                    "Phonetic": w.to_uppercase(),
+                    "PostPunct": &token[w.len()..],
+                    "PosTags": [tag],
+                })
+            })
+            .collect();
+        json!({ "Words": words })
+    });
+    (server, seen)
+}
+
+fn phonemizer(server: &MockServer, lexicon: Lexicon) -> MockPhonemizer {
+    with_word_cache(server, lexicon, 0)
+}
+
+fn with_word_cache(
+    server: &MockServer,
+    lexicon: Lexicon,
+    word_cache_capacity: usize,
+) -> MockPhonemizer {
+    let options = PhonemizerOptions {
+        lexicon: Some(lexicon),
+        word_cache_capacity,
+        ..Default::default()
+    };
+    mock_phonemizer(server, options)
+}
+
+/// Request by ISO code, the lexicon is keyed by goruut names.
+fn en_request(sentence: &str, is_reverse: bool) -> PhonemizeSentence {
+    PhonemizeSentence {
+        language: "en".to_string(),
+        is_reverse,
+        ..request(sentence)
+    }
+}
+
+#[test]
+fn overridden_words_are_replaced_in_the_whole_sentence() {
+    let (server, seen) = server();
+    let mut lexicon = Lexicon::new();
+    lexicon.insert("English", "Rustruut", "ɹʌstɹuːt").unwrap();
+    let p = phonemizer(&server, lexicon);
+
+    let resp = p
+        .sentence(en_request("I like RUSTRUUT, a lot", false))
+        .unwrap();
+    let phonetic: Vec<&str> = resp.words.iter().map(|w| w.phonetic.as_str()).collect();
+    assert_eq!(phonetic, vec!["I", "LIKE", "ɹʌstɹuːt", "A", "LOT"]);
+    assert_eq!(resp.words[2].post_punct, ",");
+    assert_eq!(*seen.lock().unwrap(), vec!["I like RUSTRUUT, a lot"]);
+
+    // nothing left for goruut
+    p.sentence(en_request("rustruut", false)).unwrap();
+    assert_eq!(server.requests(), 1);
+}
+
+#[test]
+fn with_the_word_cache_overridden_words_are_not_sent() {
+    let (server, seen) = server();
+    let mut lexicon = Lexicon::new();
+    lexicon.insert("English", "Rustruut", "ɹʌstɹuːt").unwrap();
+    let p = with_word_cache(&server, lexicon, 100);
+
+    let resp = p
+        .sentence(en_request("I like RUSTRUUT, a lot", false))
+        .unwrap();
+    let phonetic: Vec<&str> = resp.words.iter().map(|w| w.phonetic.as_str()).collect();
+    assert_eq!(phonetic, vec!["I", "LIKE", "ɹʌstɹuːt", "A", "LOT"]);
+    assert_eq!(resp.words[2].post_punct, ",");
+    assert!(resp.words[0].is_first && resp.words[4].is_last);
+    assert_eq!(*seen.lock().unwrap(), vec!["I like a lot"]);
+}
+
+#[test]
+fn tagged_entries_apply_to_matching_parts_of_speech() {
+    let (server, _) = server();
+    let mut lexicon = Lexicon::new();
+    lexicon
+        .insert_tagged("English", "read", "verb", "ɹiːd")
+        .unwrap();
+    lexicon
+        .insert_tagged("English", "lead", "VERB", "liːd")
+        .unwrap();
+    let p = phonemizer(&server, lexicon);
+
+    let resp = p.sentence(en_request("read lead", false)).unwrap();
+    assert_eq!(resp.words[0].phonetic, "ɹiːd");
+    assert_eq!(resp.words[1].phonetic, "LEAD");
+}
+
+#[test]
+fn reverse_maps_overridden_ipa_to_the_spelling() {
+    let (server, seen) = server();
+    let mut lexicon = Lexicon::new();
+    lexicon.insert("English", "Rustruut", "ɹʌstɹuːt").unwrap();
+    let p = phonemizer(&server, lexicon);
+
+    let resp = p.sentence(en_request("aɪ ɹʌstɹuːt", true)).unwrap();
+    assert_eq!(resp.words[1].phonetic, "Rustruut");
+    assert_eq!(*seen.lock().unwrap(), vec!["aɪ ɹʌstɹuːt"]);
+}
+
+#[test]
+fn replaced_pronunciations_no_longer_map_back() {
+    let (server, _) = server();
+    let mut lexicon = Lexicon::new();
+    lexicon.insert("English", "Rustruut", "ɹʌstɹuːt").unwrap();
+    lexicon.insert("English", "rustruut", "ɹʌstɹut").unwrap();
+    assert_eq!(lexicon.spelling("English", "ɹʌstɹuːt"), None);
+    assert_eq!(lexicon.spelling("English", "ɹʌstɹut"), Some("rustruut"));
+
+    // a pronunciation still used by another word maps back to that one
+    lexicon.insert("English", "read", "ɹɛd").unwrap();
+    lexicon
+        .insert_tagged("English", "red", "ADJ", "ɹɛd")
+        .unwrap();
+    lexicon.insert("English", "read", "ɹiːd").unwrap();
+    assert_eq!(lexicon.spelling("English", "ɹɛd"), Some("red"));
+
+    let p = phonemizer(&server, lexicon);
+    let resp = p.sentence(en_request("aɪ ɹʌstɹuːt", true)).unwrap();
+    // goruut's answer, not the overridden word
+    assert_eq!(resp.words[1].phonetic, "ɹʌstɹuːt".to_uppercase());
+}
+
+#[test]
+fn loads_tsv_and_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let tsv = dir.path().join("lexicon.tsv");
+    std::fs::write(
+        &tsv,
+        "# product names\nRustruut\tɹʌstɹuːt\nread\tɹɛd\tVERB\n",
+    )
+    .unwrap();
+    let json = dir.path().join("lexicon.json");
+    std::fs::write(
+        &json,
+        r#"[{"Word": "goruut", "Phonetic": "ɡoɹuːt"}, {"Word": "live", "Phonetic": "laɪv", "PosTag": "ADJ"}]"#,
+    )
+    .unwrap();
+
+    let mut lexicon = Lexicon::new();
+    lexicon.load_tsv("English", &tsv).unwrap();
+    lexicon.load_json("en", &json).unwrap();
+
+    assert_eq!(
+        lexicon.lookup("english", "rustruut", None),
+        Some("ɹʌstɹuːt")
+    );
+    assert_eq!(lexicon.lookup("English", "read", Some("VERB")), Some("ɹɛd"));
+    assert_eq!(lexicon.lookup("English", "read", None), None);
+    assert_eq!(lexicon.lookup("English", "Goruut", None), Some("ɡoɹuːt"));
+    assert_eq!(lexicon.lookup("English", "live", Some("adj")), Some("laɪv"));
+    assert_eq!(lexicon.lookup("Czech", "goruut", None), None);
+    assert_eq!(lexicon.spelling("English", "ɡoɹuːt"), Some("goruut"));
+}
+
+#[test]
+fn invalid_input_is_reported() {
+    let mut lexicon = Lexicon::new();
+    let err = lexicon
+        .parse_tsv("English", "ok\tok\nbroken line\n")
+        .unwrap_err();
+    assert!(matches!(err, LexiconError::Parse { line: 2, .. }));
+    assert!(matches!(
+        lexicon.insert("Englsh", "a", "b"),
+        Err(LexiconError::Language(_))
+    ));
+}
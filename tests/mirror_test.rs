@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use rustruut::test_util::executable_for;
+use rustruut::usecases::executable::ExecutableError;
+use rustruut::usecases::rustruut::{Goruut, RustruutError};
+use rustruut::{di, DependencyInjection, PhonemizerOptions};
+
+const CONTENT: &[u8] = b"not really goruut";
+
+fn file_url(dir: &Path) -> String {
+    reqwest::Url::from_directory_path(dir).unwrap().to_string()
+}
+
+#[test]
+fn downloads_from_file_mirror() {
+    let mirror = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let missing = file_url(&mirror.path().join("missing"));
+    let executable = executable_for(CONTENT, vec![missing, file_url(mirror.path())]);
+    std::fs::write(mirror.path().join(executable.file_name_public()), CONTENT).unwrap();
+
+    // the missing directory is skipped, the next mirror is used
+    let path = executable.download(dest.path()).unwrap();
+    assert_eq!(path, dest.path().join(executable.file_name()));
+    assert_eq!(std::fs::read(&path).unwrap(), CONTENT);
+}
+
+#[test]
+fn all_mirrors_failing_is_a_download_error() {
+    let mirror = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let executable = executable_for(CONTENT, vec![file_url(mirror.path())]);
+
+    let err = executable.download(dest.path()).unwrap_err();
+    assert!(matches!(err, ExecutableError::Download(_)), "{}", err);
+    assert!(!dest.path().join(executable.file_name()).exists());
+}
+
+#[test]
+fn releases_are_looked_up_in_the_mirror_layout() {
+    let mirror = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let public_name = executable_for(CONTENT, vec![]).file_name_public();
+    let release_dir = mirror.path().join("v0.8.0");
+    std::fs::create_dir(&release_dir).unwrap();
+    std::fs::write(release_dir.join(public_name), CONTENT).unwrap();
+
+    let di = DependencyInjection::with_parts(
+        di::default_impls::DummyPolicy,
+        di::default_impls::DummyIpaFlavor,
+        di::default_impls::DummyDict,
+        di::default_impls::DummyApi,
+        di::default_impls::DummyFolder,
+        di::custom_impls::CustomMirrors::new([file_url(mirror.path())])
+            .with_version("v0.8.0")
+            .mirrors_only(),
+    );
+    let err = Goruut::with_options(
+        di,
+        Some("v0.8.0"),
+        dest.path().to_str(),
+        HashMap::new(),
+        &PhonemizerOptions::default(),
+    )
+    .err()
+    .unwrap();
+
+    // found at <mirror>/<version>/<public name> and rejected, GitHub never tried
+    match err {
+        RustruutError::Executable(ExecutableError::Download(msg)) => {
+            assert!(msg.starts_with("Incomplete download, 17 of"), "{}", msg)
+        }
+        err => panic!("{}", err),
+    }
+}
@@ -0,0 +1,106 @@
+#![cfg(unix)]
+
+use std::collections::HashMap;
+
+use rustruut::test_util::{request, FakeExecutable};
+use rustruut::usecases::executable::ExecutableError;
+use rustruut::usecases::rustruut::{Goruut, RustruutError, EXECUTABLE_ENV};
+use rustruut::{di, DependencyInjection, Phonemizer, PhonemizerOptions};
+
+fn di<F: rustruut::interfaces::Folder>(
+    folder: F,
+) -> DependencyInjection<
+    di::default_impls::DummyPolicy,
+    di::default_impls::DummyIpaFlavor,
+    di::default_impls::DummyDict,
+    di::default_impls::DummyApi,
+    F,
+    di::default_impls::DummyVersion,
+> {
+    DependencyInjection::with_parts(
+        di::default_impls::DummyPolicy,
+        di::default_impls::DummyIpaFlavor,
+        di::default_impls::DummyDict,
+        di::default_impls::DummyApi,
+        folder,
+        di::default_impls::DummyVersion,
+    )
+}
+
+#[test]
+fn phonemizer_runs_pre_installed_executable() {
+    let fake = FakeExecutable::serve_lexicon([("hello", "həloʊ")]);
+    let dir = tempfile::tempdir().unwrap();
+    let folder = di::custom_impls::CustomExecutable::new(fake.path().to_str().unwrap())
+        .with_download_dir(dir.path().to_str().unwrap());
+
+    let phonemizer = Phonemizer::new(di(folder));
+    let resp = phonemizer.sentence(request("hello")).unwrap();
+    assert_eq!(resp.words[0].phonetic, "həloʊ");
+
+    // config written to the given folder, nothing downloaded there
+    let launches = fake.launches();
+    assert_eq!(launches.len(), 1);
+    assert!(launches[0].contains(dir.path().to_str().unwrap()));
+    let files: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert!(
+        files.iter().all(|f| f.starts_with("goruut_config_")),
+        "{:?}",
+        files
+    );
+}
+
+#[test]
+fn executable_from_environment() {
+    let fake = FakeExecutable::serve_lexicon([("world", "wɝld")]);
+    let dir = tempfile::tempdir().unwrap();
+    // the only test of this binary reading the variable
+    std::env::set_var(EXECUTABLE_ENV, fake.path());
+    let goruut = Goruut::with_options(
+        di(di::default_impls::DummyFolder),
+        None,
+        dir.path().to_str(),
+        HashMap::new(),
+        &PhonemizerOptions::default(),
+    );
+    std::env::remove_var(EXECUTABLE_ENV);
+
+    let goruut = goruut.unwrap();
+    assert_eq!(goruut.version(), None);
+    let resp = goruut.phonemize(request("world")).unwrap();
+    assert_eq!(resp.words[0].phonetic, "wɝld");
+    assert_eq!(fake.launches().len(), 1);
+}
+
+#[test]
+fn unknown_executable_fails_verification_without_running() {
+    let fake = FakeExecutable::serve_lexicon([("hello", "həloʊ")]);
+    let dir = tempfile::tempdir().unwrap();
+    let folder = di::custom_impls::CustomExecutable::new(fake.path().to_str().unwrap());
+    let options = PhonemizerOptions {
+        verify_executable: true,
+        ..Default::default()
+    };
+
+    let err = Goruut::with_options(
+        di(folder),
+        None,
+        dir.path().to_str(),
+        HashMap::new(),
+        &options,
+    )
+    .err()
+    .unwrap();
+    assert!(
+        matches!(
+            err,
+            RustruutError::Executable(ExecutableError::Verification(_))
+        ),
+        "{}",
+        err
+    );
+    assert!(fake.launches().is_empty());
+}
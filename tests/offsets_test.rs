@@ -0,0 +1,141 @@
+use rustruut::interfaces::PolicyMaxWords;
+use rustruut::models::requests::PhonemizeSentence;
+use rustruut::test_util::{mock_di, request, MockServer};
+use rustruut::{di, DependencyInjection, Phonemizer, PhonemizerOptions, WordOffsets};
+use serde_json::json;
+
+#[derive(Clone)]
+struct ThreeWordPolicy;
+impl PolicyMaxWords for ThreeWordPolicy {
+    fn get_policy_max_words(&self) -> usize {
+        3
+    }
+}
+
+/// Stand-in which, like goruut, strips punctuation, lower-cases and NFC-composes words.
+fn goruut_like() -> MockServer {
+    MockServer::start(|body| {
+        let sentence = body["Sentence"].as_str().unwrap_or_default();
+        let words: Vec<_> = sentence
+            .split_whitespace()
+            .map(|token| {
+                let clean = token.trim_matches(|c: char| c.is_ascii_punctuation() || c == '¿');
+                let pre = &token[..token.find(clean).unwrap_or(0)];
+                let post = &token[pre.len() + clean.len()..];
+                let clean = clean.to_lowercase().replace("e\u{301}", "é");
+                json!({
+                    "CleanWord": clean,
+                    "Phonetic": clean,
+                    "PrePunct": pre,
+                    "PostPunct": post,
+                })
+            })
+            .collect();
+        json!({ "Words": words })
+    })
+}
+
+fn phonemizer(
+    server: &MockServer,
+    chunking: bool,
+) -> Phonemizer<
+    ThreeWordPolicy,
+    di::default_impls::DummyIpaFlavor,
+    di::default_impls::DummyDict,
+    di::custom_impls::CustomApi,
+    di::default_impls::DummyFolder,
+    di::default_impls::DummyVersion,
+> {
+    let mock = mock_di(server);
+    let di = DependencyInjection::with_parts(
+        ThreeWordPolicy,
+        mock.ipa,
+        mock.dict_getter,
+        mock.api,
+        mock.folder,
+        mock.version,
+    );
+    let options = PhonemizerOptions {
+        chunking,
+        ..Default::default()
+    };
+    Phonemizer::with_options(di, options)
+}
+
+fn split_request(sentence: &str) -> PhonemizeSentence {
+    PhonemizeSentence {
+        split_sentences: true,
+        ..request(sentence)
+    }
+}
+
+fn spans<'s>(sentence: &'s str, offsets: &[Option<WordOffsets>]) -> Vec<&'s str> {
+    offsets
+        .iter()
+        .map(|o| o.map_or("", |o| &sentence[o.byte_start..o.byte_end]))
+        .collect()
+}
+
+#[test]
+fn offsets_skip_punctuation_and_survive_normalization() {
+    let server = goruut_like();
+    // decomposed é, goruut answers with the composed one
+    let sentence = "¿Cafe\u{301}  au   LAIT?";
+    let resp = phonemizer(&server, false)
+        .sentence(split_request(sentence))
+        .unwrap();
+    let offsets: Vec<_> = resp.words.iter().map(|w| w.offsets).collect();
+    assert_eq!(spans(sentence, &offsets), vec!["Cafe\u{301}", "au", "LAIT"]);
+    assert_eq!(
+        offsets[0],
+        Some(WordOffsets {
+            byte_start: 2,
+            byte_end: 8,
+            char_start: 1,
+            char_end: 6,
+        })
+    );
+    assert_eq!(
+        offsets[2],
+        Some(WordOffsets {
+            byte_start: 15,
+            byte_end: 19,
+            char_start: 13,
+            char_end: 17,
+        })
+    );
+}
+
+#[test]
+fn offsets_point_into_the_original_sentence_when_chunking() {
+    let server = goruut_like();
+    let sentence = "The cat sat. The cat ran, and the dog sat.";
+    let resp = phonemizer(&server, true)
+        .sentence(split_request(sentence))
+        .unwrap();
+    assert!(server.requests() > 1);
+    let offsets: Vec<_> = resp.words.iter().map(|w| w.offsets).collect();
+    assert!(offsets.iter().all(Option::is_some));
+    assert_eq!(
+        spans(sentence, &offsets),
+        vec!["The", "cat", "sat", "The", "cat", "ran", "and", "the", "dog", "sat"]
+    );
+    let starts: Vec<usize> = offsets.iter().map(|o| o.unwrap().char_start).collect();
+    assert_eq!(starts, vec![0, 4, 8, 13, 17, 21, 26, 30, 34, 38]);
+}
+
+#[test]
+fn unmatched_words_have_no_offsets() {
+    let server = MockServer::start(|_| {
+        json!({ "Words": [
+            { "CleanWord": "twelve", "Phonetic": "twɛlv" },
+            { "CleanWord": "apples", "Phonetic": "æpəlz" },
+        ]})
+    });
+    let sentence = "12 apples";
+    let resp = phonemizer(&server, false)
+        .sentence(split_request(sentence))
+        .unwrap();
+    assert_eq!(resp.words[0].offsets, None);
+    assert_eq!(spans(sentence, &[resp.words[1].offsets]), vec!["apples"]);
+}
@@ -0,0 +1,94 @@
+use rustruut::phoneme_ids::{MissingSymbol, PhonemeIdEncoder, SymbolTable};
+use rustruut::{PhonemizeSentenceResp, PhonemizeSentenceWord};
+
+fn word(pre: &str, phonetic: &str, post: &str) -> PhonemizeSentenceWord {
+    PhonemizeSentenceWord {
+        phonetic: phonetic.to_string(),
+        pre_punct: pre.to_string(),
+        post_punct: post.to_string(),
+        ..Default::default()
+    }
+}
+
+fn resp(words: Vec<PhonemizeSentenceWord>) -> PhonemizeSentenceResp {
+    PhonemizeSentenceResp {
+        words,
+        ..Default::default()
+    }
+}
+
+const PIPER: &str = r#"{
+    "audio": {"sample_rate": 22050},
+    "phoneme_id_map": {
+        "_": [0], "^": [1], "$": [2], " ": [3], "!": [4], "\"": [150], ",": [8], "?": [13],
+        "a": [14], "h": [20], "i": [21], "l": [24], "o": [27], "ʊ": [100], "ə": [59], "ˈ": [120]
+    }
+}"#;
+
+#[test]
+fn piper_intersperses_pad_between_bos_eos() {
+    let encoder = PhonemeIdEncoder::piper(SymbolTable::parse_config(PIPER).unwrap());
+    let encoded = encoder.encode(&resp(vec![
+        word("", "həˈloʊ", ","),
+        word("\"", "hi", "?\""),
+    ]));
+    assert_eq!(
+        encoded.ids,
+        vec![
+            1, 0, // ^ _
+            20, 0, 59, 0, 120, 0, 24, 0, 27, 0, 100, 0, 8, 0, // həˈloʊ,
+            3, 0, // space
+            150, 0, 20, 0, 21, 0, 13, 0, 150, 0, // "hi?"
+            2, // $
+        ]
+    );
+    assert!(encoded.missing.is_empty());
+
+    let plain = PhonemeIdEncoder::piper(SymbolTable::parse_config(PIPER).unwrap())
+        .with_punctuation(false)
+        .with_intersperse_pad(false)
+        .with_word_separator(vec![]);
+    assert_eq!(
+        plain
+            .encode(&resp(vec![word("", "hi", "!"), word("", "a", "")]))
+            .ids,
+        vec![1, 20, 21, 14, 2]
+    );
+}
+
+#[test]
+fn kokoro_vocab_and_multi_symbol_keys() {
+    let mut table =
+        SymbolTable::parse_config(r#"{"vocab": {" ": 16, "t": 62, "ʃ": 131, "i": 51}}"#).unwrap();
+    table.insert("t͡ʃ", vec![200]);
+    let encoder = PhonemeIdEncoder::kokoro(table);
+    assert_eq!(
+        encoder
+            .encode(&resp(vec![word("", "t͡ʃi", ""), word("", "ti", "")]))
+            .ids,
+        vec![0, 200, 51, 16, 62, 51, 0]
+    );
+}
+
+#[test]
+fn reports_missing_symbols() {
+    let encoder = PhonemeIdEncoder::piper(SymbolTable::parse_config(PIPER).unwrap());
+    let encoded = encoder.encode(&resp(vec![word("", "hi", ""), word("¿", "xa", "")]));
+    assert_eq!(encoded.ids, vec![1, 0, 20, 0, 21, 0, 3, 0, 14, 0, 2]);
+    assert_eq!(
+        encoded.missing,
+        vec![
+            MissingSymbol {
+                word: 1,
+                symbol: "¿".to_string()
+            },
+            MissingSymbol {
+                word: 1,
+                symbol: "x".to_string()
+            },
+        ]
+    );
+
+    assert!(SymbolTable::parse_config(r#"{"phoneme_id_map": {"a": "x"}}"#).is_err());
+    assert!(SymbolTable::parse_config(r#"{"audio": {}}"#).is_err());
+}
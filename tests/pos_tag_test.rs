@@ -0,0 +1,96 @@
+use rustruut::{PhonemizeSentenceResp, PosTag, PosTags};
+use serde_json::{json, Value};
+
+fn word(pos_tags: Value) -> PhonemizeSentenceResp {
+    serde_json::from_value(json!({
+        "Words": [{ "CleanWord": "read", "Phonetic": "ɹiːd", "PosTags": pos_tags }]
+    }))
+    .unwrap()
+}
+
+fn tags(pos_tags: Value) -> Option<Vec<PosTag>> {
+    word(pos_tags).words[0]
+        .pos_tags
+        .as_ref()
+        .map(|tags| tags.tags().to_vec())
+}
+
+#[test]
+fn parses_the_tags_of_every_shape_goruut_emits() {
+    assert_eq!(tags(json!(null)), None);
+    assert_eq!(tags(json!("VERB")), Some(vec![PosTag::Verb]));
+    assert_eq!(
+        tags(json!(["NOUN", "PROPN"])),
+        Some(vec![PosTag::Noun, PosTag::Propn])
+    );
+    assert_eq!(
+        tags(json!([["VERB"], "NNP", 3])),
+        Some(vec![
+            PosTag::Verb,
+            PosTag::Other("NNP".to_string()),
+            PosTag::Other("3".to_string())
+        ])
+    );
+    assert_eq!(tags(json!([])), Some(vec![]));
+
+    let resp: PhonemizeSentenceResp =
+        serde_json::from_value(json!({ "Words": [{ "CleanWord": "a", "Phonetic": "ə" }] }))
+            .unwrap();
+    assert!(resp.words[0].pos_tags.is_none());
+}
+
+#[test]
+fn single_tag_round_trips() {
+    round_trip(json!("VERB"));
+    round_trip(json!("verb"));
+}
+
+#[test]
+fn list_round_trips() {
+    round_trip(json!(["NOUN", "PROPN"]));
+    round_trip(json!([]));
+}
+
+#[test]
+fn nested_arrays_round_trip() {
+    round_trip(json!([["VERB"], ["noun", ["ADJ"]], "NNP"]));
+}
+
+#[test]
+fn other_values_round_trip() {
+    round_trip(json!([3, {"Tag": "VERB"}, true]));
+    round_trip(json!({"Tag": "VERB"}));
+}
+
+/// Serializing the response gives back exactly the `PosTags` goruut sent.
+fn round_trip(pos_tags: Value) {
+    let value = serde_json::to_value(word(pos_tags.clone())).unwrap();
+    assert_eq!(value["Words"][0]["PosTags"], pos_tags);
+}
+
+#[test]
+fn tags_are_recognized_in_any_case() {
+    let resp = word(json!(["verb", "Noun", "NNP"]));
+    let word = &resp.words[0];
+    assert!(word.has_pos_tag(&PosTag::Verb));
+    assert!(word.has_pos_tag(&PosTag::Noun));
+    assert!(!word.has_pos_tag(&PosTag::Adj));
+    assert_eq!(
+        word.pos_tags.as_ref().unwrap().raw(),
+        &json!(["verb", "Noun", "NNP"])
+    );
+
+    assert_eq!(PosTag::parse("cconj"), PosTag::Cconj);
+    assert_eq!(PosTag::parse("nnp"), PosTag::Other("nnp".to_string()));
+    for tag in ["ADJ", "CCONJ", "PUNCT", "X", "lowercase"] {
+        assert_eq!(PosTag::parse(tag).to_string(), tag);
+        assert_eq!(tag.parse::<PosTag>().unwrap(), PosTag::parse(tag));
+    }
+}
+
+#[test]
+fn tags_built_in_code_serialize_as_a_list() {
+    let tags = PosTags::from(vec![PosTag::Verb, PosTag::Other("NNP".to_string())]);
+    assert_eq!(serde_json::to_value(&tags).unwrap(), json!(["VERB", "NNP"]));
+    assert!(tags.contains(&PosTag::Verb));
+}
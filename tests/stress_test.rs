@@ -1,6 +1,8 @@
 use std::time::Instant;
 
+use rustruut::interfaces::{Api, DictGetter, Folder, IpaFlavor, PolicyMaxWords, Version};
 use rustruut::models::requests::PhonemizeSentence;
+use rustruut::test_util::{mock_di, MockServer};
 use rustruut::{DependencyInjection, Phonemizer};
 
 const NUM_REQUESTS: u32 = 1000;
@@ -9,9 +11,28 @@ const SENTENCE: &str = "hello world";
 
 #[test]
 fn stress_test_1000_requests() {
+    let server = MockServer::lexicon([("hello", "həloʊ"), ("world", "wɝld")]);
+    let di = mock_di(&server);
+    stress(Phonemizer::new(di));
+    assert_eq!(server.requests(), NUM_REQUESTS as usize);
+}
+
+#[test]
+#[ignore = "downloads goruut from GitHub"]
+fn stress_test_1000_requests_goruut() {
     let di: DependencyInjection = DependencyInjection::new();
-    let phonemizer = Phonemizer::new(di);
+    stress(Phonemizer::new(di));
+}
 
+fn stress<P, I, D, A, F, V>(phonemizer: Phonemizer<P, I, D, A, F, V>)
+where
+    P: PolicyMaxWords,
+    I: IpaFlavor,
+    D: DictGetter,
+    A: Api,
+    F: Folder,
+    V: Version,
+{
     let mut min_latency = f64::MAX;
     let mut max_latency = f64::MIN;
     let mut total_latency = 0.0;
@@ -0,0 +1,100 @@
+#![cfg(unix)]
+
+use std::process::Command;
+use std::time::Duration;
+
+use rustruut::test_util::{fast_restart_policy, FakeExecutable};
+use rustruut::usecases::supervisor::Supervisor;
+use rustruut::RestartPolicy;
+
+#[test]
+fn respawns_killed_child() {
+    let fake = FakeExecutable::idle();
+    let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+
+    let supervisor = Supervisor::start(exe, &config, fast_restart_policy(3)).unwrap();
+    let generation = supervisor.generation();
+    let first_pid = supervisor.id();
+
+    Command::new("kill")
+        .arg("-9")
+        .arg(first_pid.to_string())
+        .status()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(!supervisor.is_running());
+
+    assert!(supervisor.recover(generation, false).unwrap());
+    assert!(supervisor.is_running());
+    assert_ne!(supervisor.id(), first_pid);
+    assert_eq!(supervisor.restarts(), 1);
+
+    // A second caller that saw the old generation just retries
+    assert!(supervisor.recover(generation, true).unwrap());
+    assert_eq!(supervisor.restarts(), 1);
+
+    supervisor.mark_healthy();
+    assert_eq!(supervisor.restarts(), 0);
+}
+
+#[test]
+fn healthy_child_is_not_restarted_on_application_error() {
+    let fake = FakeExecutable::idle();
+    let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+
+    let supervisor = Supervisor::start(exe, &config, fast_restart_policy(3)).unwrap();
+    let pid = supervisor.id();
+
+    assert!(!supervisor.recover(supervisor.generation(), false).unwrap());
+    assert_eq!(supervisor.id(), pid);
+}
+
+#[test]
+fn restart_budget_is_enforced() {
+    // Reports serving and crashes right away
+    let fake = FakeExecutable::script("echo 'Serving...' >&2\nexit 1");
+    let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+
+    let supervisor = Supervisor::start(exe, &config, fast_restart_policy(2)).unwrap();
+    for _ in 0..2 {
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(supervisor.recover(supervisor.generation(), true).unwrap());
+    }
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(supervisor.recover(supervisor.generation(), true).is_err());
+}
+
+#[test]
+fn startup_fails_without_serving_message() {
+    let fake = FakeExecutable::failing(3);
+    let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+
+    assert!(Supervisor::start(exe, &config, fast_restart_policy(1)).is_err());
+}
+
+#[test]
+fn accessors_do_not_wait_for_a_restart() {
+    let fake = FakeExecutable::idle();
+    let (exe, config) = (fake.path(), fake.path().with_file_name("config.json"));
+    let policy = RestartPolicy {
+        max_restarts: 1,
+        initial_backoff: Duration::from_millis(500),
+        max_backoff: Duration::from_millis(500),
+    };
+
+    let supervisor = Supervisor::start(exe, &config, policy).unwrap();
+    let generation = supervisor.generation();
+    std::thread::scope(|scope| {
+        let recovery = scope.spawn(|| supervisor.recover(generation, true));
+        std::thread::sleep(Duration::from_millis(100));
+
+        // The recovery is sleeping its backoff, the state is not locked meanwhile
+        let started = std::time::Instant::now();
+        assert_eq!(supervisor.generation(), generation);
+        assert_eq!(supervisor.restarts(), 1);
+        assert!(started.elapsed() < Duration::from_millis(200));
+
+        assert!(recovery.join().unwrap().unwrap());
+    });
+    assert_eq!(supervisor.generation(), generation + 1);
+}
@@ -0,0 +1,90 @@
+use rustruut::ipa::Stress;
+use rustruut::syllable::{Syllabifier, Syllable};
+use rustruut::{PhonemizeSentenceResp, PhonemizeSentenceWord};
+
+fn split(syllabifier: &Syllabifier, ipa: &str) -> Vec<String> {
+    syllabifier
+        .syllabify(ipa)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn symbols(syllable: &Syllable) -> [String; 3] {
+    let join = |phonemes: &[rustruut::ipa::Phoneme]| {
+        phonemes.iter().map(|p| p.symbol()).collect::<String>()
+    };
+    [
+        join(&syllable.onset),
+        join(&syllable.nucleus),
+        join(&syllable.coda),
+    ]
+}
+
+#[test]
+fn maximal_onset_follows_language_phonotactics() {
+    let english = Syllabifier::new("en").unwrap();
+    assert_eq!(split(&english, "ˈæθlɛt"), vec!["ˈæθ", "lɛt"]);
+    assert_eq!(split(&english, "ˈɛkstɹə"), vec!["ˈɛk", "stɹə"]);
+    assert_eq!(split(&english, "ˈsɪŋɚ"), vec!["ˈsɪŋ", "ɚ"]);
+    assert_eq!(split(&english, "həˈloʊ"), vec!["hə", "ˈloʊ"]);
+    assert_eq!(split(&english, "ˌaɪˈdiə"), vec!["ˌaɪ", "ˈdi", "ə"]);
+    assert_eq!(split(&english, "ˈbɑtl̩"), vec!["ˈbɑ", "tl̩"]);
+
+    let generic = Syllabifier::new("Czech").unwrap();
+    assert_eq!(split(&generic, "atla"), vec!["a", "tla"]);
+    assert_eq!(split(&generic, "ˈbau̯da"), vec!["ˈbau̯", "da"]);
+    assert_eq!(split(&generic, "psst"), vec!["psst"]);
+}
+
+#[test]
+fn explicit_breaks_and_stress_win() {
+    let english = Syllabifier::new("English").unwrap();
+    assert_eq!(split(&english, "ɪk.stɹiːm"), vec!["ɪk", "stɹiːm"]);
+    assert_eq!(split(&english, "ɪks.tɹiːm"), vec!["ɪks", "tɹiːm"]);
+    assert_eq!(split(&english, "ˈa.ɪ"), vec!["ˈa", "ɪ"]);
+
+    let syllables = english.syllabify("ˌɪntɚˈnæʃənəl");
+    let stress: Vec<Stress> = syllables.iter().map(|s| s.stress).collect();
+    assert_eq!(
+        stress,
+        vec![
+            Stress::Secondary,
+            Stress::None,
+            Stress::Primary,
+            Stress::None,
+            Stress::None
+        ]
+    );
+    assert_eq!(symbols(&syllables[0]), ["", "ɪ", "n"].map(String::from));
+    assert_eq!(symbols(&syllables[2]), ["n", "æ", ""].map(String::from));
+    assert_eq!(symbols(&syllables[4]), ["n", "ə", "l"].map(String::from));
+}
+
+#[test]
+fn sentence_keeps_word_boundaries() {
+    let resp = PhonemizeSentenceResp {
+        words: vec![
+            PhonemizeSentenceWord {
+                phonetic: "ðə".to_string(),
+                ..Default::default()
+            },
+            PhonemizeSentenceWord {
+                phonetic: "ˈtɛkst".to_string(),
+                ..Default::default()
+            },
+            PhonemizeSentenceWord::default(),
+        ],
+        ..Default::default()
+    };
+    let syllables = Syllabifier::new("English").unwrap().sentence(&resp);
+    assert_eq!(syllables.len(), 3);
+    assert_eq!(syllables[0].len(), 1);
+    assert_eq!(
+        symbols(&syllables[1][0]),
+        ["t", "ɛ", "kst"].map(String::from)
+    );
+    assert!(syllables[2].is_empty());
+
+    assert!(Syllabifier::new("Klingon").is_err());
+}
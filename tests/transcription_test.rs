@@ -0,0 +1,89 @@
+use rustruut::transcription::{ConversionError, Converter, Notation, Unmappable};
+use rustruut::{PhonemizeSentenceResp, PhonemizeSentenceWord};
+
+#[test]
+fn arpabet_marks_stress_on_vowels() {
+    let arpabet = Converter::new(Notation::Arpabet);
+    assert_eq!(arpabet.from_ipa("ˈwɔːtɚ").unwrap(), "W AO1 T ER0");
+    assert_eq!(arpabet.from_ipa("ˈt͡ʃɪɹz").unwrap(), "CH IH1 R Z");
+    assert_eq!(arpabet.from_ipa("ˌaɪˈdiə").unwrap(), "AY2 D IY1 AH0");
+    assert_eq!(arpabet.from_ipa("ˈbɑtl̩").unwrap(), "B AA1 T AH0 L");
+    assert_eq!(
+        arpabet.from_ipa("həˈloʊ ˈwɝld").unwrap(),
+        "HH AH0 L OW1 | W ER1 L D"
+    );
+    assert_eq!(
+        arpabet.to_ipa("HH AH0 L OW1 | W ER1 L D").unwrap(),
+        "həlˈoʊ wˈɝld"
+    );
+}
+
+#[test]
+fn xsampa_and_kirshenbaum_round_trip() {
+    let ipa = "ˈθɪŋkɪŋ ʃʊd͡ʒ ðæt ˈɹɛd";
+    for (notation, expected) in [
+        (Notation::XSampa, "\"TINkIN SUd_Z D{t \"r\\Ed"),
+        (Notation::Kirshenbaum, "'TINkIN SUdZ D&t 'rEd"),
+    ] {
+        let converter = Converter::new(notation);
+        let ascii = converter.from_ipa(ipa).unwrap();
+        assert_eq!(ascii, expected);
+        let back = converter.to_ipa(&ascii).unwrap();
+        match notation {
+            // Kirshenbaum has no tie bar
+            Notation::Kirshenbaum => assert_eq!(back, ipa.replace('\u{0361}', "")),
+            _ => assert_eq!(back, ipa),
+        }
+    }
+}
+
+#[test]
+fn unmappable_symbols_follow_the_policy() {
+    let ipa = "ʔaʁ";
+    let arpabet = Converter::new(Notation::Arpabet);
+    assert_eq!(arpabet.from_ipa(ipa).unwrap(), "ʔ AA0 ʁ");
+    let dropped = arpabet.clone().with_unmappable(Unmappable::Drop);
+    assert_eq!(dropped.from_ipa(ipa).unwrap(), "AA0");
+    let replaced = arpabet
+        .clone()
+        .with_unmappable(Unmappable::Replace("?".to_string()));
+    assert_eq!(replaced.from_ipa(ipa).unwrap(), "? AA0 ?");
+    let strict = arpabet.with_unmappable(Unmappable::Fail);
+    assert_eq!(
+        strict.from_ipa(ipa),
+        Err(ConversionError {
+            symbol: "ʔ".to_string(),
+            notation: Notation::Arpabet,
+        })
+    );
+
+    let kirshenbaum = Converter::new(Notation::Kirshenbaum).with_unmappable(Unmappable::Fail);
+    assert!(kirshenbaum.from_ipa("ɓ").is_err());
+    assert!(kirshenbaum.to_ipa("a$").is_err());
+}
+
+#[test]
+fn converts_every_word_of_a_response() {
+    let resp = PhonemizeSentenceResp {
+        words: vec![
+            PhonemizeSentenceWord {
+                clean_word: "hello".to_string(),
+                phonetic: "həˈloʊ".to_string(),
+                post_punct: ",".to_string(),
+                ..Default::default()
+            },
+            PhonemizeSentenceWord {
+                clean_word: "world".to_string(),
+                phonetic: "ˈwɝld".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let converted = Converter::new(Notation::XSampa)
+        .convert_response(&resp)
+        .unwrap();
+    assert_eq!(converted.words[0].phonetic, "h@\"loU");
+    assert_eq!(converted.words[0].post_punct, ",");
+    assert_eq!(converted.words[1].phonetic, "\"w3`ld");
+}
@@ -0,0 +1,79 @@
+use rustruut::test_util::{mock_phonemizer, request, MockPhonemizer, MockServer};
+use rustruut::PhonemizerOptions;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+fn phonemizer(server: &MockServer, capacity: usize) -> MockPhonemizer {
+    let options = PhonemizerOptions {
+        word_cache_capacity: capacity,
+        ..Default::default()
+    };
+    mock_phonemizer(server, options)
+}
+
+/// Echo stand-in which records every sentence it was asked for.
+fn recording_server() -> (MockServer, Arc<Mutex<Vec<String>>>) {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    let server = MockServer::start(move |body| {
+        let sentence = body["Sentence"].as_str().unwrap_or_default();
+        log.lock().unwrap().push(sentence.to_string());
+        let words: Vec<_> = sentence
+            .split_whitespace()
+            .map(|w| json!({ "CleanWord": w, "Phonetic": w.to_uppercase() }))
+            .collect();
+        json!({ "Words": words })
+    });
+    (server, seen)
+}
+
+#[test]
+fn known_words_are_not_sent_again() {
+    let (server, seen) = recording_server();
+    let p = phonemizer(&server, 100);
+
+    p.sentence(request("the cat sat")).unwrap();
+    let resp = p.sentence(request("the dog sat")).unwrap();
+
+    assert_eq!(*seen.lock().unwrap(), vec!["the cat sat", "dog"]);
+    let phonetic: Vec<&str> = resp.words.iter().map(|w| w.phonetic.as_str()).collect();
+    assert_eq!(phonetic, vec!["THE", "DOG", "SAT"]);
+    let first: Vec<bool> = resp.words.iter().map(|w| w.is_first).collect();
+    let last: Vec<bool> = resp.words.iter().map(|w| w.is_last).collect();
+    assert_eq!(first, vec![true, false, false]);
+    assert_eq!(last, vec![false, false, true]);
+
+    // Fully cached sentences never reach goruut
+    p.sentence(request("sat the dog")).unwrap();
+    assert_eq!(server.requests(), 2);
+
+    let stats = p.word_cache_stats().unwrap();
+    assert_eq!(stats.hits, 5);
+    assert_eq!(stats.misses, 4);
+    assert_eq!(stats.len, 4);
+    assert!((stats.hit_rate() - 5.0 / 9.0).abs() < 1e-9);
+}
+
+#[test]
+fn least_recently_used_words_are_evicted() {
+    let (server, seen) = recording_server();
+    let p = phonemizer(&server, 2);
+
+    p.sentence(request("a b")).unwrap();
+    p.sentence(request("a")).unwrap();
+    p.sentence(request("c")).unwrap();
+    p.sentence(request("a b")).unwrap();
+
+    assert_eq!(*seen.lock().unwrap(), vec!["a b", "c", "b"]);
+    assert_eq!(p.word_cache_stats().unwrap().len, 2);
+}
+
+#[test]
+fn disabled_by_default() {
+    let server = MockServer::echo();
+    let p = phonemizer(&server, 0);
+    p.sentence(request("hello")).unwrap();
+    p.sentence(request("hello")).unwrap();
+    assert_eq!(server.requests(), 2);
+    assert!(p.word_cache_stats().is_none());
+}